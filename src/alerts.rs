@@ -0,0 +1,470 @@
+// src/alerts.rs
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Duration, Utc};
+use std::{collections::{HashMap, HashSet, VecDeque}, fs};
+use crate::app::Resource;
+use crate::config::config_path;
+use crate::errors::ClientError;
+use crate::models::{QueueMap, WorkerPings, WorkerStatuses, WorkerVersions};
+
+/// Resolved alerts kept for the Alerts tab
+pub const MAX_RESOLVED: usize = 100;
+/// How long `s` silences an alert
+pub const SILENCE_MINUTES: i64 = 60;
+
+/// A rule from alerts.toml
+///
+/// ```toml
+/// [[rules]]
+/// name = "gpu-1 silent"
+/// kind = "ping_stale"
+/// worker = "gpu-1"
+/// seconds = 60
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Rule {
+    /// Display name; defaults to the kind
+    pub name: Option<String>,
+    #[serde(flatten)]
+    pub condition: Condition,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Condition {
+    /// A worker has not pinged in `seconds`
+    PingStale { worker: Option<String>, seconds: i64 },
+    /// A model queue stayed above `threshold` for `minutes`
+    QueueAbove { model: Option<String>, threshold: usize, #[serde(default)] minutes: i64 },
+    /// A worker is missing from the status list; without `worker`, any
+    /// worker seen earlier in the session
+    WorkerMissing { worker: Option<String> },
+    /// Workers report different hive or ollama versions
+    VersionMismatch,
+}
+
+impl Rule {
+    pub fn name(&self) -> String {
+        self.name.clone().unwrap_or_else(|| match &self.condition {
+            Condition::PingStale { .. } => "ping_stale".into(),
+            Condition::QueueAbove { .. } => "queue_above".into(),
+            Condition::WorkerMissing { .. } => "worker_missing".into(),
+            Condition::VersionMismatch => "version_mismatch".into(),
+        })
+    }
+
+    /// Resource the rule is evaluated from
+    pub fn resource(&self) -> Resource {
+        match self.condition {
+            Condition::PingStale { .. } => Resource::Pings,
+            Condition::QueueAbove { .. } => Resource::Queue,
+            Condition::WorkerMissing { .. } => Resource::Statuses,
+            Condition::VersionMismatch => Resource::Versions,
+        }
+    }
+}
+
+/// Wrapper for the alerts file
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct AlertsFile {
+    #[serde(default)]
+    rules: Vec<Rule>,
+}
+
+/// Load alert rules from alerts.toml next to profiles.toml
+pub fn load_rules() -> Result<Vec<Rule>, ClientError> {
+    let path = config_path("alerts.toml")?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let contents = fs::read_to_string(path)?;
+    let file: AlertsFile = toml::from_str(&contents)?;
+    Ok(file.rules)
+}
+
+/// A fired (and possibly resolved) alert
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Alert {
+    /// Rule position, rule name and subject, unique while the alert is active
+    pub id: String,
+    pub rule: String,
+    /// Worker, model or component the alert is about
+    pub subject: String,
+    pub message: String,
+    pub fired_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub acked: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum AlertEvent {
+    Fired(Alert),
+    Resolved(Alert),
+}
+
+impl AlertEvent {
+    pub fn alert(&self) -> &Alert {
+        match self {
+            AlertEvent::Fired(a) | AlertEvent::Resolved(a) => a,
+        }
+    }
+}
+
+/// The cached management data rules are evaluated against
+pub struct ClusterView<'a> {
+    pub statuses: Option<&'a WorkerStatuses>,
+    pub pings: Option<&'a WorkerPings>,
+    pub queue: Option<&'a QueueMap>,
+    pub versions: Option<&'a WorkerVersions>,
+}
+
+/// Rule evaluation state: active alerts, recent resolutions and silences
+#[derive(Debug, Default)]
+pub struct AlertEngine {
+    pub rules: Vec<Rule>,
+    /// Active alerts by id
+    pub active: HashMap<String, Alert>,
+    /// Resolved alerts, newest last
+    pub resolved: VecDeque<Alert>,
+    /// Silenced alert ids and when the silence ends
+    pub silenced: HashMap<String, DateTime<Utc>>,
+    /// When a duration-based condition first became true, by alert id
+    pending: HashMap<String, DateTime<Utc>>,
+    /// Workers seen in any status poll
+    seen_workers: HashSet<String>,
+}
+
+impl AlertEngine {
+    pub fn new(rules: Vec<Rule>) -> Self {
+        AlertEngine { rules, ..Default::default() }
+    }
+
+    /// Whether some rule needs `resource` polled regardless of the open tab
+    pub fn needs(&self, resource: Resource) -> bool {
+        self.rules.iter().any(|r| r.resource() == resource)
+    }
+
+    /// Forget the state of the previous cluster, keeping rules
+    pub fn reset(&mut self) {
+        *self = AlertEngine::new(std::mem::take(&mut self.rules));
+    }
+
+    pub fn is_silenced(&self, id: &str, now: DateTime<Utc>) -> bool {
+        self.silenced.get(id).is_some_and(|until| *until > now)
+    }
+
+    pub fn acknowledge(&mut self, id: &str) {
+        if let Some(alert) = self.active.get_mut(id) {
+            alert.acked = true;
+        }
+    }
+
+    /// Silence an alert for `SILENCE_MINUTES`, or lift an existing silence
+    pub fn toggle_silence(&mut self, id: &str, now: DateTime<Utc>) -> bool {
+        if self.is_silenced(id, now) {
+            self.silenced.remove(id);
+            false
+        } else {
+            self.silenced.insert(id.to_string(), now + Duration::minutes(SILENCE_MINUTES));
+            true
+        }
+    }
+
+    /// Active alerts first (oldest first), then resolved ones (newest first)
+    pub fn listed(&self) -> Vec<&Alert> {
+        let mut active: Vec<&Alert> = self.active.values().collect();
+        active.sort_by(|a, b| a.fired_at.cmp(&b.fired_at).then_with(|| a.id.cmp(&b.id)));
+        active.into_iter().chain(self.resolved.iter().rev()).collect()
+    }
+
+    /// Evaluate every rule whose data was just polled
+    pub fn evaluate(&mut self, now: DateTime<Utc>, resource: Resource, view: &ClusterView) -> Vec<AlertEvent> {
+        if resource == Resource::Statuses {
+            if let Some(statuses) = view.statuses {
+                self.seen_workers.extend(statuses.keys().filter(|w| *w != "Unauthenticated").cloned());
+            }
+        }
+        self.silenced.retain(|_, until| *until > now);
+
+        let mut events = Vec::new();
+        for (index, rule) in self.rules.clone().into_iter().enumerate() {
+            if rule.resource() != resource {
+                continue;
+            }
+            let name = rule.name();
+            // Names repeat (unnamed rules of one kind share theirs), positions do not
+            let prefix = format!("{}:{}/", index, name);
+            let Some(firing) = self.check(&rule, &prefix, now, view) else { continue };
+            let firing_ids: HashSet<String> = firing.iter().map(|(s, _)| format!("{}{}", prefix, s)).collect();
+
+            let resolved: Vec<String> = self.active.keys()
+                .filter(|id| id.starts_with(&prefix) && !firing_ids.contains(*id))
+                .cloned()
+                .collect();
+            for id in resolved {
+                if let Some(mut alert) = self.active.remove(&id) {
+                    alert.resolved_at = Some(now);
+                    if self.resolved.len() == MAX_RESOLVED {
+                        self.resolved.pop_front();
+                    }
+                    self.resolved.push_back(alert.clone());
+                    events.push(AlertEvent::Resolved(alert));
+                }
+            }
+
+            for (subject, message) in firing {
+                let id = format!("{}{}", prefix, subject);
+                if let Some(alert) = self.active.get_mut(&id) {
+                    alert.message = message;
+                    continue;
+                }
+                let alert = Alert {
+                    id: id.clone(),
+                    rule: name.clone(),
+                    subject,
+                    message,
+                    fired_at: now,
+                    resolved_at: None,
+                    acked: false,
+                };
+                self.active.insert(id, alert.clone());
+                events.push(AlertEvent::Fired(alert));
+            }
+        }
+        events
+    }
+
+    /// (subject, message) pairs the rule currently fires for, or `None`
+    /// if the data it needs has not been polled yet. `prefix` starts the
+    /// ids of the rule's alerts.
+    fn check(&mut self, rule: &Rule, prefix: &str, now: DateTime<Utc>, view: &ClusterView) -> Option<Vec<(String, String)>> {
+        let mut firing = Vec::new();
+        match &rule.condition {
+            Condition::PingStale { worker, seconds } => {
+                let pings = view.pings?;
+                for (name, times) in pings {
+                    if name == "Unauthenticated" || worker.as_ref().is_some_and(|w| w != name) {
+                        continue;
+                    }
+                    let age = times.last().map(|t| (now - *t).num_seconds());
+                    if age.is_none_or(|age| age > *seconds) {
+                        let since = age.map(|a| format!("{}s", a)).unwrap_or_else(|| "ever".into());
+                        firing.push((name.clone(), format!("{} has not pinged in {}", name, since)));
+                    }
+                }
+            }
+            Condition::QueueAbove { model, threshold, minutes } => {
+                let queue = view.queue?;
+                for (key, count) in queue {
+                    if key.starts_with("Node:") {
+                        continue;
+                    }
+                    let name = key.trim_start_matches("Model:").trim();
+                    if model.as_ref().is_some_and(|m| m != name) {
+                        continue;
+                    }
+                    let id = format!("{}{}", prefix, name);
+                    if count <= threshold {
+                        self.pending.remove(&id);
+                        continue;
+                    }
+                    let since = *self.pending.entry(id).or_insert(now);
+                    if now - since >= Duration::minutes(*minutes) {
+                        firing.push((name.to_string(), format!("Queue for {} is {} (> {})", name, count, threshold)));
+                    }
+                }
+                // Queues that emptied out are no longer listed
+                self.pending.retain(|id, _| {
+                    !id.starts_with(prefix)
+                        || queue.keys().any(|k| k.trim_start_matches("Model:").trim() == &id[prefix.len()..])
+                });
+            }
+            Condition::WorkerMissing { worker } => {
+                let statuses = view.statuses?;
+                let expected: Vec<&String> = match worker {
+                    Some(w) => vec![w],
+                    None => self.seen_workers.iter().collect(),
+                };
+                for name in expected {
+                    if !statuses.contains_key(name) {
+                        firing.push((name.clone(), format!("{} is missing from the worker list", name)));
+                    }
+                }
+            }
+            Condition::VersionMismatch => {
+                let versions = view.versions?;
+                let hive: HashSet<&str> = versions.values().map(|v| v.hive.as_str()).collect();
+                let ollama: HashSet<&str> = versions.values().map(|v| v.ollama.as_str()).collect();
+                for (component, set) in [("hive", hive), ("ollama", ollama)] {
+                    if set.len() > 1 {
+                        let mut found: Vec<&str> = set.into_iter().collect();
+                        found.sort();
+                        firing.push((component.to_string(), format!("Workers run different {} versions: {}", component, found.join(", "))));
+                    }
+                }
+            }
+        }
+        Some(firing)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+    use serde_json::json;
+
+    use super::*;
+
+    fn at(seconds: i64) -> DateTime<Utc> {
+        DateTime::UNIX_EPOCH + TimeDelta::seconds(seconds)
+    }
+
+    fn engine(condition: Condition) -> AlertEngine {
+        AlertEngine::new(vec![Rule { name: None, condition }])
+    }
+
+    fn view<'a>(
+        statuses: Option<&'a WorkerStatuses>,
+        pings: Option<&'a WorkerPings>,
+        queue: Option<&'a QueueMap>,
+        versions: Option<&'a WorkerVersions>,
+    ) -> ClusterView<'a> {
+        ClusterView { statuses, pings, queue, versions }
+    }
+
+    /// (fired, subject) of each event
+    fn summary(events: &[AlertEvent]) -> Vec<(bool, String)> {
+        events.iter().map(|e| (matches!(e, AlertEvent::Fired(_)), e.alert().subject.clone())).collect()
+    }
+
+    #[test]
+    fn stale_pings_fire_and_resolve() {
+        let mut engine = engine(Condition::PingStale { worker: None, seconds: 60 });
+        let pings: WorkerPings = HashMap::from([
+            ("gpu-01".into(), vec![at(0)]),
+            ("gpu-02".into(), vec![]),
+            ("Unauthenticated".into(), vec![]),
+        ]);
+        let events = engine.evaluate(at(30), Resource::Pings, &view(None, Some(&pings), None, None));
+        assert_eq!(summary(&events), [(true, "gpu-02".to_string())]);
+
+        let events = engine.evaluate(at(90), Resource::Pings, &view(None, Some(&pings), None, None));
+        assert_eq!(summary(&events), [(true, "gpu-01".to_string())]);
+        assert_eq!(engine.active["0:ping_stale/gpu-01"].message, "gpu-01 has not pinged in 90s");
+
+        let pings: WorkerPings = HashMap::from([("gpu-01".into(), vec![at(100)]), ("gpu-02".into(), vec![at(100)])]);
+        let mut events = summary(&engine.evaluate(at(110), Resource::Pings, &view(None, Some(&pings), None, None)));
+        events.sort();
+        assert_eq!(events, [(false, "gpu-01".to_string()), (false, "gpu-02".to_string())]);
+        assert!(engine.active.is_empty());
+        assert_eq!(engine.resolved.len(), 2);
+    }
+
+    #[test]
+    fn rules_of_one_kind_keep_their_own_alerts() {
+        let mut engine = AlertEngine::new(vec![
+            Rule { name: None, condition: Condition::PingStale { worker: None, seconds: 30 } },
+            Rule { name: None, condition: Condition::PingStale { worker: None, seconds: 120 } },
+        ]);
+        let pings: WorkerPings = HashMap::from([("gpu-01".into(), vec![at(0)])]);
+        let events = engine.evaluate(at(60), Resource::Pings, &view(None, Some(&pings), None, None));
+        assert_eq!(summary(&events), [(true, "gpu-01".to_string())]);
+
+        let events = engine.evaluate(at(150), Resource::Pings, &view(None, Some(&pings), None, None));
+        assert_eq!(summary(&events), [(true, "gpu-01".to_string())]);
+        let events = engine.evaluate(at(160), Resource::Pings, &view(None, Some(&pings), None, None));
+        assert!(events.is_empty());
+        let mut ids: Vec<&String> = engine.active.keys().collect();
+        ids.sort();
+        assert_eq!(ids, ["0:ping_stale/gpu-01", "1:ping_stale/gpu-01"]);
+    }
+
+    #[test]
+    fn rules_wait_for_their_data_and_resource() {
+        let mut engine = engine(Condition::PingStale { worker: None, seconds: 60 });
+        let pings: WorkerPings = HashMap::from([("gpu-01".into(), vec![])]);
+        assert!(engine.evaluate(at(0), Resource::Pings, &view(None, None, None, None)).is_empty());
+        assert!(engine.evaluate(at(0), Resource::Queue, &view(None, Some(&pings), None, None)).is_empty());
+        assert!(engine.active.is_empty());
+    }
+
+    #[test]
+    fn queues_fire_only_after_staying_above_the_threshold() {
+        let mut engine = engine(Condition::QueueAbove { model: None, threshold: 2, minutes: 5 });
+        let long: QueueMap = HashMap::from([("Model: llama3".into(), 4), ("Node: gpu-01".into(), 9)]);
+        let short: QueueMap = HashMap::from([("Model: llama3".into(), 1)]);
+        let evaluate = |engine: &mut AlertEngine, seconds, queue| {
+            summary(&engine.evaluate(at(seconds), Resource::Queue, &view(None, None, Some(queue), None)))
+        };
+
+        assert!(evaluate(&mut engine, 0, &long).is_empty());
+        // Dropping below the threshold restarts the wait
+        assert!(evaluate(&mut engine, 120, &short).is_empty());
+        assert!(evaluate(&mut engine, 180, &long).is_empty());
+        assert!(evaluate(&mut engine, 400, &long).is_empty());
+        assert_eq!(evaluate(&mut engine, 480, &long), [(true, "llama3".to_string())]);
+        assert_eq!(engine.active["0:queue_above/llama3"].message, "Queue for llama3 is 4 (> 2)");
+        assert_eq!(evaluate(&mut engine, 490, &QueueMap::new()), [(false, "llama3".to_string())]);
+    }
+
+    #[test]
+    fn workers_seen_earlier_are_missed() {
+        let mut engine = engine(Condition::WorkerMissing { worker: None });
+        let both: WorkerStatuses = HashMap::from([
+            ("gpu-01".into(), vec!["Polling".into()]),
+            ("gpu-02".into(), vec!["Polling".into()]),
+            ("Unauthenticated".into(), vec![]),
+        ]);
+        let one: WorkerStatuses = HashMap::from([("gpu-01".into(), vec!["Polling".into()])]);
+        assert!(engine.evaluate(at(0), Resource::Statuses, &view(Some(&both), None, None, None)).is_empty());
+        let events = engine.evaluate(at(10), Resource::Statuses, &view(Some(&one), None, None, None));
+        assert_eq!(summary(&events), [(true, "gpu-02".to_string())]);
+        let events = engine.evaluate(at(20), Resource::Statuses, &view(Some(&both), None, None, None));
+        assert_eq!(summary(&events), [(false, "gpu-02".to_string())]);
+    }
+
+    #[test]
+    fn a_named_worker_is_missed_without_being_seen() {
+        let mut engine = engine(Condition::WorkerMissing { worker: Some("gpu-09".into()) });
+        let statuses: WorkerStatuses = HashMap::from([("gpu-01".into(), vec!["Polling".into()])]);
+        let events = engine.evaluate(at(0), Resource::Statuses, &view(Some(&statuses), None, None, None));
+        assert_eq!(summary(&events), [(true, "gpu-09".to_string())]);
+    }
+
+    #[test]
+    fn differing_versions_fire_per_component() {
+        let mut engine = engine(Condition::VersionMismatch);
+        let versions: WorkerVersions = serde_json::from_value(json!({
+            "gpu-01": { "hive": "0.4.2", "ollama": "0.6.5" },
+            "gpu-02": { "hive": "0.4.1", "ollama": "0.6.5" },
+        })).unwrap();
+        let events = engine.evaluate(at(0), Resource::Versions, &view(None, None, None, Some(&versions)));
+        assert_eq!(summary(&events), [(true, "hive".to_string())]);
+        assert_eq!(engine.active["0:version_mismatch/hive"].message, "Workers run different hive versions: 0.4.1, 0.4.2");
+    }
+
+    #[test]
+    fn acks_hold_while_the_alert_stays_active() {
+        let mut engine = engine(Condition::WorkerMissing { worker: Some("gpu-09".into()) });
+        let statuses = WorkerStatuses::new();
+        engine.evaluate(at(0), Resource::Statuses, &view(Some(&statuses), None, None, None));
+        engine.acknowledge("0:worker_missing/gpu-09");
+        let events = engine.evaluate(at(10), Resource::Statuses, &view(Some(&statuses), None, None, None));
+        assert!(events.is_empty());
+        assert!(engine.active["0:worker_missing/gpu-09"].acked);
+    }
+
+    #[test]
+    fn silences_expire_and_toggle_off() {
+        let mut engine = AlertEngine::default();
+        assert!(engine.toggle_silence("a", at(0)));
+        assert!(engine.is_silenced("a", at(SILENCE_MINUTES * 60 - 1)));
+        assert!(!engine.is_silenced("a", at(SILENCE_MINUTES * 60)));
+        assert!(!engine.toggle_silence("a", at(10)));
+        assert!(!engine.is_silenced("a", at(10)));
+
+        engine.toggle_silence("b", at(0));
+        engine.evaluate(at(SILENCE_MINUTES * 60), Resource::Statuses, &view(None, None, None, None));
+        assert!(engine.silenced.is_empty());
+    }
+}
@@ -1,8 +1,17 @@
+use std::collections::{BTreeSet, HashMap};
+use std::time::{Duration, Instant};
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
 use tokio::task::AbortHandle;
 
 // src/app.rs
-use crate::config::Profile;
+use crate::config::{save_profiles, Profile};
+use crate::alerts::{AlertEngine, AlertEvent, ClusterView};
+use crate::history::History;
+use crate::notify::{EventKind, Notifier, NotifyEvent};
 use crate::models::*;
+use crate::errors::ClientError;
+use crate::recording::{Recorder, Replay};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Focus {
@@ -21,6 +30,8 @@ pub enum ActionPanelState {
     DeleteModel, // Same
     Confirmation(String, ActionType), // Model name to confirm
     Response(String, ActionType, Vec<String>, bool),
+    /// Progress of a model action running on several workers, rows in `App::batch`
+    Batch(String, ActionType),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -29,12 +40,280 @@ pub enum ActionType {
     Delete,
 }
 
+/// Workers a pull or delete is sent to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionTarget {
+    /// The highlighted worker only
+    Selected,
+    /// Every worker marked with Space
+    Marked,
+    /// Every authenticated worker
+    All,
+    /// Workers lacking the model (pull) or holding it (delete)
+    Eligible,
+}
+
+impl ActionTarget {
+    pub fn next(self) -> Self {
+        match self {
+            ActionTarget::Selected => ActionTarget::Marked,
+            ActionTarget::Marked => ActionTarget::All,
+            ActionTarget::All => ActionTarget::Eligible,
+            ActionTarget::Eligible => ActionTarget::Selected,
+        }
+    }
+
+    pub fn label(self, action_type: ActionType) -> &'static str {
+        match (self, action_type) {
+            (ActionTarget::Selected, _) => "selected worker",
+            (ActionTarget::Marked, _) => "marked workers",
+            (ActionTarget::All, _) => "all workers",
+            (ActionTarget::Eligible, ActionType::Pull) => "all workers lacking the model",
+            (ActionTarget::Eligible, ActionType::Delete) => "all workers holding the model",
+        }
+    }
+}
+
+/// Progress of one worker in a multi-worker action
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BatchState {
+    /// Waiting for a free slot under the parallelism limit
+    Queued,
+    /// Running, with the last status line reported
+    Running(String),
+    Done(String),
+    Failed(String),
+}
+
+/// One row of the multi-worker action table
+#[derive(Debug, Clone)]
+pub struct BatchRow {
+    pub worker: String,
+    pub state: BatchState,
+}
+
+
+/// Interaction style of the Console tab
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsoleMode {
+    /// Single prompt → single completion via /api/generate
+    Generate,
+    /// Multi-turn conversation via /api/chat
+    Chat,
+}
+
+/// A chat conversation with its own system prompt
+#[derive(Debug, Clone, Default)]
+pub struct Conversation {
+    pub system_prompt: String,
+    pub messages: Vec<ChatMessage>,
+}
+
+impl Conversation {
+    /// Messages to send to /api/chat, with the system prompt first if set
+    pub fn request_messages(&self) -> Vec<ChatMessage> {
+        let mut messages = Vec::with_capacity(self.messages.len() + 1);
+        if !self.system_prompt.trim().is_empty() {
+            messages.push(ChatMessage {
+                role: "system".into(),
+                content: self.system_prompt.clone(),
+            });
+        }
+        messages.extend(self.messages.iter().cloned());
+        messages
+    }
+
+    /// Remove the reply placeholder of a stream that ended before any text
+    pub fn drop_empty_reply(&mut self) {
+        if self.messages.last().is_some_and(|m| m.role == "assistant" && m.content.is_empty()) {
+            self.messages.pop();
+        }
+    }
+}
+
+/// Step of the Console model/node picker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickerStage {
+    Model,
+    Node,
+}
+
+/// Overlay state for choosing the Console model and worker
+#[derive(Debug, Clone)]
+pub struct ModelPicker {
+    pub stage: PickerStage,
+    /// Known models, merged from `worker_tags` and `/api/models`
+    pub models: Vec<String>,
+    /// Workers offering the chosen model; index 0 of the list is "any worker"
+    pub nodes: Vec<String>,
+    pub selected: usize,
+}
+
+impl ModelPicker {
+    /// Number of rows in the current stage, including the "any worker" row
+    pub fn row_count(&self) -> usize {
+        match self.stage {
+            PickerStage::Model => self.models.len(),
+            PickerStage::Node => self.nodes.len() + 1,
+        }
+    }
+
+    /// Add models not yet listed, keeping the list sorted
+    pub fn merge_models(&mut self, models: impl IntoIterator<Item = String>) {
+        for model in models {
+            if !self.models.contains(&model) {
+                self.models.push(model);
+            }
+        }
+        self.models.sort();
+    }
+}
+
+/// Latest fleet poll result for one profile
+#[derive(Debug, Clone)]
+pub struct ClusterState {
+    /// Last successful summary, kept while the cluster is unreachable
+    pub summary: Option<ClusterSummary>,
+    /// Error of the last poll; `None` means reachable
+    pub error: Option<String>,
+    pub updated: DateTime<Utc>,
+}
+
+impl ClusterState {
+    pub fn is_reachable(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Labels of the editable profile fields, in form order
+pub const PROFILE_FIELDS: [&str; 6] = [
+    "Name",
+    "Host",
+    "Inference port",
+    "Management port",
+    "Client token",
+    "Admin token",
+];
+
+/// Form state for adding or editing a profile
+#[derive(Debug, Clone)]
+pub struct ProfileEditor {
+    /// Profile being edited; `None` adds a new one
+    pub index: Option<usize>,
+    /// Field values in `PROFILE_FIELDS` order
+    pub fields: [String; 6],
+    /// Field under the cursor
+    pub field: usize,
+}
+
+impl ProfileEditor {
+    pub fn new(index: Option<usize>, profile: Option<&Profile>) -> Self {
+        let fields = match profile {
+            Some(p) => [
+                p.name.clone(),
+                p.host.clone(),
+                p.port_infer.to_string(),
+                p.port_manage.to_string(),
+                p.client_token.clone(),
+                p.admin_token.clone(),
+            ],
+            None => [
+                String::new(),
+                String::new(),
+                "6666".into(),
+                "6668".into(),
+                String::new(),
+                String::new(),
+            ],
+        };
+        ProfileEditor { index, fields, field: 0 }
+    }
+
+    /// Validate the form and build the profile
+    pub fn to_profile(&self) -> Result<Profile, String> {
+        let name = self.fields[0].trim();
+        if name.is_empty() {
+            return Err("Profile name cannot be empty.".into());
+        }
+        let host = self.fields[1].trim();
+        if host.is_empty() {
+            return Err("Host cannot be empty.".into());
+        }
+        let port_infer = self.fields[2].trim().parse()
+            .map_err(|_| format!("Invalid inference port '{}'.", self.fields[2]))?;
+        let port_manage = self.fields[3].trim().parse()
+            .map_err(|_| format!("Invalid management port '{}'.", self.fields[3]))?;
+        Ok(Profile {
+            name: name.to_string(),
+            host: host.to_string(),
+            port_infer,
+            port_manage,
+            client_token: self.fields[4].trim().to_string(),
+            admin_token: self.fields[5].trim().to_string(),
+        })
+    }
+}
+
+/// Profiles overlay: list of profiles with an optional open editor
+#[derive(Debug, Clone)]
+pub struct ProfilesView {
+    pub selected: usize,
+    pub editor: Option<ProfileEditor>,
+    /// Waiting for y/n before deleting the selected profile
+    pub confirm_delete: bool,
+}
+
+/// Throughput of a streamed Console response
+#[derive(Debug, Clone)]
+pub struct GenerationStats {
+    pub started: Instant,
+    pub first_token: Option<Instant>,
+    pub finished: Option<Instant>,
+    /// Streamed chunks received so far (one token each)
+    pub tokens: usize,
+    /// Rate reported by the server in the final chunk, if any
+    pub reported_tps: Option<f64>,
+}
+
+impl GenerationStats {
+    pub fn new() -> Self {
+        GenerationStats {
+            started: Instant::now(),
+            first_token: None,
+            finished: None,
+            tokens: 0,
+            reported_tps: None,
+        }
+    }
+
+    /// Tokens per second, measured from the first token until now or the end
+    pub fn tokens_per_second(&self) -> Option<f64> {
+        if let Some(tps) = self.reported_tps {
+            return Some(tps);
+        }
+        let first = self.first_token?;
+        let end = self.finished.unwrap_or_else(Instant::now);
+        let secs = end.duration_since(first).as_secs_f64();
+        if secs > 0.0 {
+            Some(self.tokens as f64 / secs)
+        } else {
+            None
+        }
+    }
+}
 
 /// Application tabs
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Tab {
     Dashboard,
+    Nodes,
+    Queues,
+    Keys,
+    Logs,
     Console,
+    Fleet,
+    Alerts,
+    Models,
 }
 
 impl Tab {
@@ -42,9 +321,152 @@ impl Tab {
     pub fn all() -> &'static [Tab] {
         &[
             Tab::Dashboard,
+            Tab::Nodes,
+            Tab::Queues,
+            Tab::Keys,
+            Tab::Logs,
             Tab::Console,
+            Tab::Fleet,
+            Tab::Alerts,
+            Tab::Models,
         ]
     }
+
+    /// Label shown in the tab bar
+    pub fn title(&self) -> &'static str {
+        match self {
+            Tab::Dashboard => "Dashboard",
+            Tab::Nodes => "Nodes",
+            Tab::Queues => "Queues",
+            Tab::Keys => "Keys",
+            Tab::Logs => "Logs",
+            Tab::Console => "Console",
+            Tab::Fleet => "Fleet",
+            Tab::Alerts => "Alerts",
+            Tab::Models => "Models",
+        }
+    }
+
+    /// Management API resources the tab needs refreshed on every tick
+    pub fn resources(&self) -> &'static [Resource] {
+        match self {
+            Tab::Dashboard => &[
+                Resource::Statuses,
+                Resource::Connections,
+                Resource::Pings,
+                Resource::Versions,
+                Resource::Tags,
+                Resource::Queue,
+            ],
+            Tab::Nodes => &[
+                Resource::Statuses,
+                Resource::Connections,
+                Resource::Pings,
+                Resource::Versions,
+            ],
+            Tab::Queues => &[Resource::Queue],
+            Tab::Keys => &[Resource::Keys],
+            Tab::Logs => &[Resource::Pings, Resource::Connections],
+            Tab::Console => &[Resource::Queue, Resource::Tags],
+            // Every profile is polled by the fleet poller instead
+            Tab::Fleet => &[],
+            // Rule inputs are polled on every tab, see `AlertEngine::needs`
+            Tab::Alerts => &[],
+            Tab::Models => &[Resource::Tags, Resource::Queue, Resource::Statuses],
+        }
+    }
+}
+
+/// Data sets exposed by the HiveCore management API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Resource {
+    Queue,
+    Statuses,
+    Connections,
+    Pings,
+    Versions,
+    Tags,
+    Keys,
+}
+
+impl Resource {
+    /// Return all resources in polling order
+    pub fn all() -> &'static [Resource] {
+        &[
+            Resource::Queue,
+            Resource::Statuses,
+            Resource::Connections,
+            Resource::Pings,
+            Resource::Versions,
+            Resource::Tags,
+            Resource::Keys,
+        ]
+    }
+
+    /// Short name for status lines and error messages
+    pub fn label(&self) -> &'static str {
+        match self {
+            Resource::Queue => "queue",
+            Resource::Statuses => "worker statuses",
+            Resource::Connections => "connections",
+            Resource::Pings => "pings",
+            Resource::Versions => "versions",
+            Resource::Tags => "models",
+            Resource::Keys => "keys",
+        }
+    }
+}
+
+/// Result of the latest poll of one management endpoint
+#[derive(Debug, Clone, PartialEq)]
+pub enum EndpointHealth {
+    /// Answered at this time
+    Ok(DateTime<Utc>),
+    /// The last request failed with this error
+    Failing(String),
+    /// The endpoint's circuit is open; no answer since this time
+    Unreachable(DateTime<Utc>),
+}
+
+/// Connection to HiveCore, summarised over the polled endpoints
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConnectionStatus {
+    /// No endpoint has answered or failed yet
+    Connecting,
+    Online,
+    /// Some endpoints fail while others answer
+    Degraded(Vec<Resource>),
+    /// HiveCore answers, but with errors only, e.g. for a rejected token
+    Failing(Vec<Resource>),
+    /// No endpoint answers; HiveCore went away at this time
+    Offline(DateTime<Utc>),
+}
+
+/// A freshly polled payload for one resource
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "resource", content = "data", rename_all = "snake_case")]
+pub enum PollData {
+    Queue(QueueMap),
+    Statuses(WorkerStatuses),
+    Connections(WorkerConnections),
+    Pings(WorkerPings),
+    Versions(WorkerVersions),
+    Tags(WorkerTags),
+    Keys(AuthKeys),
+}
+
+impl PollData {
+    pub fn resource(&self) -> Resource {
+        match self {
+            PollData::Queue(_) => Resource::Queue,
+            PollData::Statuses(_) => Resource::Statuses,
+            PollData::Connections(_) => Resource::Connections,
+            PollData::Pings(_) => Resource::Pings,
+            PollData::Versions(_) => Resource::Versions,
+            PollData::Tags(_) => Resource::Tags,
+            PollData::Keys(_) => Resource::Keys,
+        }
+    }
 }
 
 /// Holds the shared application state
@@ -81,6 +503,18 @@ pub struct App {
     pub action_panel_scroll: u16, // NEW: For scrolling action panel response
     pub action_task_handle: Option<AbortHandle>, // NEW: To cancel background action tasks
     pub is_action_in_progress: bool,
+    /// Workers marked in the workers list for multi-worker actions
+    pub marked_workers: BTreeSet<String>,
+    /// Workers the next pull or delete is sent to
+    pub action_target: ActionTarget,
+    /// Most workers a multi-worker action runs on at once
+    pub action_parallelism: usize,
+    /// Per-worker rows of the running or last multi-worker action
+    pub batch: Vec<BatchRow>,
+    /// Layer downloads of the running or last single-worker pull
+    pub pull_progress: PullProgress,
+    /// Last response line and how often it arrived in a row
+    action_line_repeat: Option<(String, usize)>,
 
     // Cached data for tabs
     pub worker_versions: Option<WorkerVersions>,
@@ -90,12 +524,69 @@ pub struct App {
     pub worker_tags: Option<WorkerTags>,
     pub queue_map: Option<QueueMap>,
     pub auth_keys: Option<AuthKeys>,
-    pub generate_response: Option<GenerateResponse>,
     pub console_output: Vec<String>,
+
+    /// Whether Enter in the Console generates or chats
+    pub console_mode: ConsoleMode,
+    /// Chat conversations; forks are appended to the end
+    pub conversations: Vec<Conversation>,
+    /// Index of the conversation shown in the Console
+    pub active_conversation: usize,
+    /// Lines scrolled up from the bottom of the Console output
+    pub console_scroll: u16,
+    /// True while the Console input edits the system prompt
+    pub editing_system_prompt: bool,
+    /// True while a generate or chat stream is running
+    pub is_generating: bool,
+    /// Handle of the running Console stream, used to cancel it
+    pub console_task_handle: Option<AbortHandle>,
+    /// Token counters for the current or last Console stream
+    pub generation_stats: Option<GenerationStats>,
+    /// Model chosen in the picker; falls back to the first queued model
+    pub console_model_choice: Option<String>,
+    /// Worker the Console requests are pinned to (`Node` header)
+    pub console_node: Option<String>,
+    /// Open model/node picker overlay
+    pub model_picker: Option<ModelPicker>,
+    /// Open profiles overlay
+    pub profiles_view: Option<ProfilesView>,
+    /// Time series of the active profile's polled data
+    pub history: History,
+    /// Profile `history` belongs to; `None` until the first profile is activated
+    history_profile: Option<String>,
+    /// Store history under the data directory across sessions
+    pub persist_history: bool,
+    /// Write profile changes to profiles.toml; off for the demo profile
+    pub persist_profiles: bool,
+    /// Worker shown in the Dashboard detail screen
+    pub worker_detail: Option<String>,
+    /// Scroll offset of the detail screen's ping timeline
+    pub worker_detail_scroll: u16,
+    /// Selected (model row, worker column) in the Models tab
+    pub models_cursor: (usize, usize),
+    /// Alert rules and the alerts they fired on the active profile
+    pub alerts: AlertEngine,
+    /// Selected row in the Alerts tab
+    pub alerts_selected: usize,
+    /// Delivers alert and action events to the configured sinks
+    pub notifier: Notifier,
+    /// Fleet overview per profile name, filled while the Fleet tab is open
+    pub fleet: HashMap<String, ClusterState>,
+    /// Selected row (profile index) in the Fleet tab
+    pub fleet_selected: usize,
+    /// Outcome of the latest poll per resource of the active profile
+    pub endpoint_health: HashMap<Resource, EndpointHealth>,
+    /// Appends every polled snapshot to the `--record` file
+    pub recorder: Option<Recorder>,
+    /// Recording played instead of polling HiveCore (`--replay`)
+    pub replay: Option<Replay>,
+    /// Bumped whenever the active connection settings change, so background
+    /// tasks can rebuild their clients and drop results for the old profile
+    pub profile_epoch: u64,
 }
 
 /// Configurable polling intervals
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Intervals {
     /// High-frequency interval for queues (0.5s)
     pub queue_secs: f32,
@@ -103,6 +594,28 @@ pub struct Intervals {
     pub general_secs: u64,
 }
 
+impl Intervals {
+    /// Polling cadence for a single resource
+    pub fn for_resource(&self, resource: Resource) -> Duration {
+        match resource {
+            Resource::Queue => Duration::from_secs_f32(self.queue_secs),
+            _ => Duration::from_secs(self.general_secs),
+        }
+    }
+
+    /// Halve or double the queue interval, bounded to [0.1s, 60s]
+    pub fn scale_queue(&mut self, faster: bool) {
+        let next = if faster { self.queue_secs / 2.0 } else { self.queue_secs * 2.0 };
+        self.queue_secs = next.clamp(0.1, 60.0);
+    }
+
+    /// Step the general interval by one second, bounded to [1s, 300s]
+    pub fn step_general(&mut self, faster: bool) {
+        let next = if faster { self.general_secs.saturating_sub(1) } else { self.general_secs + 1 };
+        self.general_secs = next.clamp(1, 300);
+    }
+}
+
 impl Default for Intervals {
     fn default() -> Self {
         Intervals {
@@ -130,8 +643,35 @@ impl App {
             worker_tags: None,
             queue_map: None,
             auth_keys: None,
-            generate_response: None,
             console_output: Vec::new(),
+            console_mode: ConsoleMode::Generate,
+            conversations: vec![Conversation::default()],
+            active_conversation: 0,
+            console_scroll: 0,
+            editing_system_prompt: false,
+            is_generating: false,
+            console_task_handle: None,
+            generation_stats: None,
+            console_model_choice: None,
+            console_node: None,
+            model_picker: None,
+            profiles_view: None,
+            history: History::default(),
+            history_profile: None,
+            persist_history: false,
+            persist_profiles: true,
+            models_cursor: (0, 0),
+            worker_detail: None,
+            worker_detail_scroll: 0,
+            alerts: AlertEngine::default(),
+            alerts_selected: 0,
+            notifier: Notifier::default(),
+            fleet: HashMap::new(),
+            fleet_selected: 0,
+            endpoint_health: HashMap::new(),
+            recorder: None,
+            replay: None,
+            profile_epoch: 0,
             focus: Focus::WorkersList,
             selected_worker: 0,
             worker_actions: vec!["Pull model", "Delete model"],
@@ -143,6 +683,12 @@ impl App {
             action_panel_scroll: 0, // Initialize scroll to 0
             action_task_handle: None, // No task running initially
             is_action_in_progress: false, // Not in progress
+            marked_workers: BTreeSet::new(),
+            action_target: ActionTarget::Selected,
+            action_parallelism: 4,
+            batch: Vec::new(),
+            pull_progress: PullProgress::default(),
+            action_line_repeat: None,
         }
     }
 
@@ -155,6 +701,13 @@ impl App {
         }
     }
 
+    /// Jump to the tab at `index` in `Tab::all()`
+    pub fn select_tab(&mut self, index: usize) {
+        if let Some(tab) = Tab::all().get(index) {
+            self.current_tab = *tab;
+        }
+    }
+
     /// Cycle to the previous tab
     pub fn prev_tab(&mut self) {
         let tabs = Tab::all();
@@ -168,7 +721,500 @@ impl App {
     pub fn set_active_profile(&mut self, index: usize) {
         if index < self.profiles.len() {
             self.active_profile = index;
+            self.profile_epoch += 1;
+            self.console_model_choice = None;
+            self.console_node = None;
+            self.selected_worker = 0;
+            self.worker_detail = None;
+            self.marked_workers.clear();
+            self.endpoint_health.clear();
             self.clear_caches();
+            self.switch_history();
+            self.alerts.reset();
+            self.alerts_selected = 0;
+        }
+    }
+
+    /// Store the current history and load the one of the active profile
+    fn switch_history(&mut self) {
+        self.save_history();
+        let name = self.profiles[self.active_profile].name.clone();
+        self.history = if self.persist_history {
+            History::load(&name).unwrap_or_else(|e| {
+                self.add_banner(format!("Can't load metrics history: {}", e));
+                History::default()
+            })
+        } else {
+            History::default()
+        };
+        self.history_profile = Some(name);
+    }
+
+    /// Write the history to disk if persistence is enabled
+    pub fn save_history(&mut self) {
+        if !self.persist_history {
+            return;
+        }
+        if let Some(name) = &self.history_profile {
+            if let Err(e) = self.history.save(name) {
+                self.add_banner(format!("Can't save metrics history: {}", e));
+            }
+        }
+    }
+
+    /// Switch to the cluster selected in the Fleet tab and show its Dashboard
+    pub fn drill_into_cluster(&mut self) {
+        let index = self.fleet_selected;
+        if index != self.active_profile {
+            self.set_active_profile(index);
+        }
+        self.current_tab = Tab::Dashboard;
+    }
+
+    /// Open the detail screen of the selected worker
+    pub fn open_worker_detail(&mut self) {
+        if let Some(name) = self.get_selected_worker_name() {
+            self.worker_detail = Some(name);
+            self.worker_detail_scroll = 0;
+        }
+    }
+
+    /// Open the profiles overlay on the active profile
+    pub fn open_profiles_view(&mut self) {
+        self.profiles_view = Some(ProfilesView {
+            selected: self.active_profile,
+            editor: None,
+            confirm_delete: false,
+        });
+    }
+
+    /// Insert or replace a profile from the editor and persist the list.
+    /// Editing the active profile reconnects with the new settings.
+    pub fn save_profile(&mut self, index: Option<usize>, profile: Profile) -> Result<usize, String> {
+        let duplicate = self.profiles.iter().enumerate()
+            .any(|(i, p)| p.name == profile.name && Some(i) != index);
+        if duplicate {
+            return Err(format!("A profile named '{}' already exists.", profile.name));
+        }
+        // Edit a copy so a failed save leaves the list as it is on disk
+        let mut profiles = self.profiles.clone();
+        let saved_index = match index {
+            Some(i) if i < profiles.len() => {
+                profiles[i] = profile;
+                i
+            }
+            _ => {
+                profiles.push(profile);
+                profiles.len() - 1
+            }
+        };
+        if self.persist_profiles {
+            save_profiles(&profiles).map_err(|e| format!("Failed to save profiles: {}", e))?;
+        }
+        self.profiles = profiles;
+        if saved_index == self.active_profile {
+            self.set_active_profile(saved_index);
+        }
+        Ok(saved_index)
+    }
+
+    /// Remove a profile and persist the list; the last profile cannot be removed
+    pub fn delete_profile(&mut self, index: usize) -> Result<(), String> {
+        if self.profiles.len() <= 1 {
+            return Err("Cannot delete the only profile.".into());
+        }
+        if index >= self.profiles.len() {
+            return Ok(());
+        }
+        let mut profiles = self.profiles.clone();
+        profiles.remove(index);
+        if self.persist_profiles {
+            save_profiles(&profiles).map_err(|e| format!("Failed to save profiles: {}", e))?;
+        }
+        self.profiles = profiles;
+        if index == self.active_profile {
+            self.set_active_profile(0);
+        } else if index < self.active_profile {
+            // Same profile, shifted position: no reconnect needed
+            self.active_profile -= 1;
+        }
+        Ok(())
+    }
+
+    /// Store a polled payload in the matching cache, record it in the history
+    /// and evaluate the alert rules that depend on it
+    pub fn apply_poll(&mut self, data: PollData) {
+        self.apply_poll_at(data, Utc::now());
+    }
+
+    /// `apply_poll` for a payload polled at `now`, e.g. one being replayed
+    pub fn apply_poll_at(&mut self, data: PollData, now: DateTime<Utc>) {
+        let resource = data.resource();
+        if let Some(recorder) = &mut self.recorder {
+            let profile = &self.profiles[self.active_profile].name;
+            if let Err(e) = recorder.record(now, profile, &data) {
+                self.recorder = None;
+                self.add_banner(format!("Recording stopped: {}", e));
+            }
+        }
+        match data {
+            PollData::Queue(v) => {
+                self.history.record_queue(now, &v);
+                self.queue_map = Some(v);
+            }
+            PollData::Statuses(v) => {
+                self.history.record_statuses(now, &v);
+                self.worker_statuses = Some(v);
+            }
+            PollData::Connections(v) => {
+                self.history.record_connections(now, &v);
+                self.worker_connections = Some(v);
+            }
+            PollData::Pings(v) => self.worker_pings = Some(v),
+            PollData::Versions(v) => self.worker_versions = Some(v),
+            PollData::Tags(v) => self.worker_tags = Some(v),
+            PollData::Keys(v) => self.auth_keys = Some(v),
+        }
+        self.endpoint_health.insert(resource, EndpointHealth::Ok(now));
+        self.evaluate_alerts(resource, now);
+    }
+
+    /// The time views measure ages and windows against: the replay clock
+    /// while replaying, else the wall clock
+    pub fn now(&self) -> DateTime<Utc> {
+        self.replay.as_ref().map_or_else(Utc::now, |replay| replay.position)
+    }
+
+    /// Run the replay clock for `elapsed` wall time and apply the snapshots
+    /// it passed
+    pub fn step_replay(&mut self, elapsed: Duration) {
+        let Some(replay) = &mut self.replay else { return };
+        replay.advance(elapsed);
+        for snapshot in replay.due() {
+            self.apply_poll_at(snapshot.data, snapshot.at);
+        }
+    }
+
+    /// Move the replay clock by `by`. Going back rebuilds the state from the
+    /// start of the recording.
+    pub fn seek_replay(&mut self, by: TimeDelta) {
+        let Some(replay) = &mut self.replay else { return };
+        if replay.seek(by) {
+            self.restart_replay();
+        }
+        self.step_replay(Duration::ZERO);
+    }
+
+    /// Pause or resume the replay
+    pub fn toggle_replay(&mut self) {
+        let Some(replay) = &mut self.replay else { return };
+        if replay.toggle() {
+            self.restart_replay();
+        }
+        self.step_replay(Duration::ZERO);
+    }
+
+    /// Forget everything applied so far in the replay
+    fn restart_replay(&mut self) {
+        self.clear_caches();
+        self.endpoint_health.clear();
+        self.history = History::default();
+        self.alerts.reset();
+    }
+
+    /// Record a failed poll of `resource`. Cached data stays as it was. An
+    /// error HiveCore answered with, such as a rejected token, also shows a
+    /// banner the first time; outages only show in the connection status.
+    pub fn record_poll_error(&mut self, resource: Resource, error: &ClientError) {
+        let health = match (error, self.endpoint_health.get(&resource)) {
+            (ClientError::CircuitOpen { since, .. }, _) => EndpointHealth::Unreachable(*since),
+            // A failed probe of an open circuit keeps the outage going
+            (e, Some(EndpointHealth::Unreachable(since))) if e.is_transient() => EndpointHealth::Unreachable(*since),
+            (e, _) => EndpointHealth::Failing(e.to_string()),
+        };
+        let previous = self.endpoint_health.insert(resource, health.clone());
+        if !error.is_transient() && previous.as_ref() != Some(&health) {
+            self.add_banner(format!("Can't load {}: {}", resource.label(), error));
+        }
+    }
+
+    /// Whether the pollers fetch `resource` now: the current tab shows it or
+    /// an alert rule watches it
+    pub fn is_polled(&self, resource: Resource) -> bool {
+        self.current_tab.resources().contains(&resource) || self.alerts.needs(resource)
+    }
+
+    /// Summarise `endpoint_health` into one connection state
+    pub fn connection_status(&self) -> ConnectionStatus {
+        // Results of resources no longer polled, e.g. those of a tab left
+        // since, say nothing about HiveCore now. While nothing polled has a
+        // result yet, as on the Fleet tab, the latest results stand in.
+        let any_polled = self.endpoint_health.keys().any(|r| self.is_polled(*r));
+        let health: Vec<&EndpointHealth> = self.endpoint_health.iter()
+            .filter(|(r, _)| !any_polled || self.is_polled(**r))
+            .map(|(_, h)| h)
+            .collect();
+        if health.is_empty() {
+            return ConnectionStatus::Connecting;
+        }
+        let answering = health.iter().any(|h| matches!(h, EndpointHealth::Ok(_)));
+        let unreachable_since = health.iter()
+            .filter_map(|h| match h {
+                EndpointHealth::Unreachable(since) => Some(*since),
+                _ => None,
+            })
+            .min();
+        if let (false, Some(since)) = (answering, unreachable_since) {
+            return ConnectionStatus::Offline(since);
+        }
+        let failing: Vec<Resource> = Resource::all().iter()
+            .copied()
+            .filter(|r| !any_polled || self.is_polled(*r))
+            .filter(|r| matches!(self.endpoint_health.get(r), Some(EndpointHealth::Failing(_) | EndpointHealth::Unreachable(_))))
+            .collect();
+        match (failing.is_empty(), answering) {
+            (true, _) => ConnectionStatus::Online,
+            (false, true) => ConnectionStatus::Degraded(failing),
+            (false, false) => ConnectionStatus::Failing(failing),
+        }
+    }
+
+    /// Text for a view whose `resource` has no data yet: why it failed, or
+    /// that it is still loading
+    pub fn placeholder(&self, resource: Resource) -> String {
+        match self.endpoint_health.get(&resource) {
+            Some(EndpointHealth::Failing(message)) => format!("Can't load {}: {}", resource.label(), message),
+            Some(EndpointHealth::Unreachable(since)) => format!(
+                "Can't load {}: HiveCore unreachable since {}",
+                resource.label(),
+                since.with_timezone(&chrono::Local).format("%H:%M:%S"),
+            ),
+            _ => format!("Loading {}...", resource.label()),
+        }
+    }
+
+    fn evaluate_alerts(&mut self, resource: Resource, now: DateTime<Utc>) {
+        let view = ClusterView {
+            statuses: self.worker_statuses.as_ref(),
+            pings: self.worker_pings.as_ref(),
+            queue: self.queue_map.as_ref(),
+            versions: self.worker_versions.as_ref(),
+        };
+        let events = self.alerts.evaluate(now, resource, &view);
+        for event in events {
+            if self.alerts.is_silenced(&event.alert().id, now) {
+                continue;
+            }
+            let (kind, msg) = match &event {
+                AlertEvent::Fired(a) => (EventKind::AlertFired, format!("ALERT [{}] {}", a.rule, a.message)),
+                AlertEvent::Resolved(a) => (EventKind::AlertResolved, format!("Resolved [{}] {}", a.rule, a.subject)),
+            };
+            let alert = event.alert();
+            self.notify(kind, &alert.id, &alert.message);
+            self.add_banner(msg);
+        }
+    }
+
+    /// Queue an event on the active profile for the notification sinks
+    pub fn notify(&self, kind: EventKind, subject: &str, message: &str) {
+        self.notifier.send(NotifyEvent {
+            kind,
+            profile: self.profiles[self.active_profile].name.clone(),
+            subject: subject.to_string(),
+            message: message.to_string(),
+            at: Utc::now(),
+        });
+    }
+
+    /// The alert selected in the Alerts tab
+    fn selected_alert_id(&self) -> Option<String> {
+        self.alerts.listed().get(self.alerts_selected).map(|a| a.id.clone())
+    }
+
+    /// Acknowledge the selected active alert
+    pub fn acknowledge_selected_alert(&mut self) {
+        if let Some(id) = self.selected_alert_id() {
+            self.alerts.acknowledge(&id);
+        }
+    }
+
+    /// Silence the selected alert for an hour, or lift its silence
+    pub fn toggle_silence_selected_alert(&mut self) {
+        if let Some(id) = self.selected_alert_id() {
+            let msg = if self.alerts.toggle_silence(&id, self.now()) {
+                format!("Silenced {} for {} minutes.", id, crate::alerts::SILENCE_MINUTES)
+            } else {
+                format!("Silence lifted for {}.", id)
+            };
+            self.add_banner(msg);
+        }
+    }
+
+    /// Model used for Console inference: the picked one, else the first model with a queue
+    pub fn console_model(&self) -> Option<String> {
+        if let Some(model) = &self.console_model_choice {
+            return Some(model.clone());
+        }
+        let queues = self.queue_map.as_ref()?;
+        let mut models: Vec<&String> = queues.keys()
+            .filter(|k| !k.starts_with("Node:"))
+            .collect();
+        models.sort();
+        models.first().map(|k| k.trim_start_matches("Model:").trim().to_string())
+    }
+
+    /// Models advertised by any worker, sorted and deduplicated
+    pub fn known_models(&self) -> Vec<String> {
+        let mut models: Vec<String> = self.worker_tags.as_ref()
+            .map(|tags| tags.values().flatten().cloned().collect())
+            .unwrap_or_default();
+        models.sort();
+        models.dedup();
+        models
+    }
+
+    /// Workers whose tags include `model`, sorted by name
+    pub fn workers_with_model(&self, model: &str) -> Vec<String> {
+        let mut workers: Vec<String> = self.worker_tags.as_ref()
+            .map(|tags| tags.iter()
+                .filter(|(_, models)| models.iter().any(|m| m == model))
+                .map(|(worker, _)| worker.clone())
+                .collect())
+            .unwrap_or_default();
+        workers.sort();
+        workers
+    }
+
+    /// Open the model picker, preselecting the current model
+    pub fn open_model_picker(&mut self) {
+        let models = self.known_models();
+        let selected = self.console_model()
+            .and_then(|current| models.iter().position(|m| *m == current))
+            .unwrap_or(0);
+        self.model_picker = Some(ModelPicker {
+            stage: PickerStage::Model,
+            models,
+            nodes: Vec::new(),
+            selected,
+        });
+    }
+
+    /// Move the picker cursor
+    pub fn move_picker(&mut self, down: bool) {
+        if let Some(picker) = &mut self.model_picker {
+            let max = picker.row_count().saturating_sub(1);
+            picker.selected = if down {
+                (picker.selected + 1).min(max)
+            } else {
+                picker.selected.saturating_sub(1)
+            };
+        }
+    }
+
+    /// Confirm the highlighted row: a model advances to the node step,
+    /// a node (or "any worker") finishes the choice
+    pub fn confirm_picker(&mut self) {
+        let Some(picker) = self.model_picker.take() else { return };
+        match picker.stage {
+            PickerStage::Model => {
+                let Some(model) = picker.models.get(picker.selected).cloned() else {
+                    self.add_banner("No models known yet.");
+                    return;
+                };
+                let nodes = self.workers_with_model(&model);
+                let selected = self.console_node.as_ref()
+                    .and_then(|n| nodes.iter().position(|w| w == n))
+                    .map(|i| i + 1)
+                    .unwrap_or(0);
+                self.console_model_choice = Some(model);
+                self.model_picker = Some(ModelPicker {
+                    stage: PickerStage::Node,
+                    nodes,
+                    selected,
+                    ..picker
+                });
+            }
+            PickerStage::Node => {
+                self.console_node = match picker.selected {
+                    0 => None,
+                    i => picker.nodes.get(i - 1).cloned(),
+                };
+            }
+        }
+    }
+
+    /// Toggle the Console between generate and chat
+    pub fn toggle_console_mode(&mut self) {
+        self.console_mode = match self.console_mode {
+            ConsoleMode::Generate => ConsoleMode::Chat,
+            ConsoleMode::Chat => ConsoleMode::Generate,
+        };
+        self.console_scroll = 0;
+        self.editing_system_prompt = false;
+    }
+
+    /// The conversation currently shown in the Console
+    pub fn conversation(&self) -> &Conversation {
+        &self.conversations[self.active_conversation]
+    }
+
+    pub fn conversation_mut(&mut self) -> &mut Conversation {
+        &mut self.conversations[self.active_conversation]
+    }
+
+    /// Drop all messages of the active conversation, keeping its system prompt
+    pub fn clear_conversation(&mut self) {
+        self.conversation_mut().messages.clear();
+        self.console_scroll = 0;
+    }
+
+    /// Copy the active conversation into a new one and switch to it
+    pub fn fork_conversation(&mut self) {
+        let fork = self.conversation().clone();
+        self.conversations.push(fork);
+        self.active_conversation = self.conversations.len() - 1;
+        self.console_scroll = 0;
+    }
+
+    /// Cycle through conversations
+    pub fn cycle_conversation(&mut self, forward: bool) {
+        let len = self.conversations.len();
+        self.active_conversation = if forward {
+            (self.active_conversation + 1) % len
+        } else {
+            (self.active_conversation + len - 1) % len
+        };
+        self.console_scroll = 0;
+    }
+
+    /// Abort the running Console stream, if any
+    pub fn cancel_generation(&mut self) {
+        if let Some(handle) = self.console_task_handle.take() {
+            handle.abort();
+        }
+        if self.is_generating {
+            self.is_generating = false;
+            if let Some(stats) = &mut self.generation_stats {
+                stats.finished = Some(Instant::now());
+            }
+            // Otherwise the placeholder goes out as history with the next message
+            for conversation in &mut self.conversations {
+                conversation.drop_empty_reply();
+            }
+        }
+    }
+
+    /// Append streamed text to the Console output, starting new lines on '\n'
+    pub fn append_console_output(&mut self, text: &str) {
+        let mut parts = text.split('\n');
+        if let Some(first) = parts.next() {
+            match self.console_output.last_mut() {
+                Some(last) => last.push_str(first),
+                None => self.console_output.push(first.to_string()),
+            }
+        }
+        for part in parts {
+            self.console_output.push(part.to_string());
         }
     }
 
@@ -191,6 +1237,7 @@ impl App {
             self.add_banner("Cancelled active action task.");
         }
         self.is_action_in_progress = false; // Ensure flag is reset
+        self.cancel_generation();
         self.worker_versions = None;
         self.worker_statuses = None;
         self.worker_connections = None;
@@ -198,7 +1245,6 @@ impl App {
         self.worker_tags = None;
         self.queue_map = None;
         self.auth_keys = None;
-        self.generate_response = None;
         self.console_output.clear();
         self.console_input.clear();
         self.action_panel_state = ActionPanelState::None;
@@ -257,6 +1303,7 @@ impl App {
                 match selected_action_name {
                     Some("Pull model") => {
                          self.action_panel_state = ActionPanelState::PullModel;
+                         self.reset_action_target();
                          self.focus = Focus::ActionPanelInput;
                          self.action_input_model_name.clear();
                          self.action_input_cursor_position = 0;
@@ -264,6 +1311,7 @@ impl App {
                     },
                     Some("Delete model") => {
                          self.action_panel_state = ActionPanelState::DeleteModel;
+                         self.reset_action_target();
                          self.focus = Focus::ActionPanelInput;
                          self.action_input_model_name.clear();
                          self.action_input_cursor_position = 0;
@@ -368,10 +1416,61 @@ impl App {
             self.action_input_cursor_position -= 1;
         }
     }
+    /// Show the response panel of a starting single-worker action
+    pub fn begin_action_response(&mut self, model_name: String, action_type: ActionType) {
+        self.action_panel_state = ActionPanelState::Response(
+            model_name,
+            action_type,
+            vec!["Initiating action...".to_string()],
+            true,
+        );
+        self.pull_progress = PullProgress::default();
+        self.action_line_repeat = None;
+    }
+
+    /// Show one event of the running single-worker action in the response panel
+    pub fn apply_action_event(&mut self, event: ActionEvent) {
+        let verb = match self.action_panel_state {
+            ActionPanelState::Response(_, ActionType::Delete, _, _) => "delete",
+            _ => "pull",
+        };
+        match event {
+            ActionEvent::Status(line) => self.add_action_output_line(line, true),
+            // Byte counts go to the progress gauges, not the log
+            ActionEvent::Progress(line) => {
+                self.pull_progress.update(&line, Instant::now());
+            }
+            ActionEvent::LineError(message) => {
+                self.add_banner(format!("Model {} error: {}", verb, message));
+                self.add_action_output_line(message, false);
+            }
+            ActionEvent::Finished { success: true } => {
+                self.add_action_output_line(format!("Model {} completed successfully.", verb), true);
+            }
+            ActionEvent::Finished { success: false } => {
+                self.add_action_output_line(format!("Model {} completed with errors.", verb), false);
+            }
+        }
+    }
+
+    /// Append a response line; a line equal to the previous one bumps its
+    /// repeat counter instead
     pub fn add_action_output_line(&mut self, line: String, is_success: bool) {
         match &mut self.action_panel_state {
             ActionPanelState::Response(_, _, ref mut output_lines, ref mut current_is_success) => {
-                output_lines.push(line);
+                match &mut self.action_line_repeat {
+                    Some((last, count)) if *last == line => {
+                        *count += 1;
+                        let collapsed = format!("{} (x{})", line, count);
+                        if let Some(previous) = output_lines.last_mut() {
+                            *previous = collapsed;
+                        }
+                    }
+                    _ => {
+                        output_lines.push(line.clone());
+                        self.action_line_repeat = Some((line, 1));
+                    }
+                }
                 *current_is_success = is_success; // Update overall status based on latest line
             },
             _ => {
@@ -395,12 +1494,174 @@ impl App {
     }
 
     pub fn get_selected_worker_name(&self) -> Option<String> {
-        self.worker_statuses.as_ref()
-            .and_then(|map| {
-                let mut names: Vec<_> = map.keys().filter(|&n| n != "Unauthenticated").cloned().collect();
-                names.sort();
-                names.get(self.selected_worker).cloned()
-            })
+        self.worker_names().get(self.selected_worker).cloned()
+    }
+
+    /// Authenticated workers in the order of the Dashboard workers list
+    pub fn worker_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.worker_statuses.as_ref()
+            .map(|map| map.keys().filter(|&n| n != "Unauthenticated").cloned().collect())
+            .unwrap_or_default();
+        names.sort();
+        names
+    }
+
+    /// Move the Models tab cursor, staying inside the matrix
+    pub fn move_models_cursor(&mut self, rows: isize, cols: isize) {
+        let max_row = self.known_models().len().saturating_sub(1);
+        let max_col = self.worker_names().len().saturating_sub(1);
+        let (row, col) = self.models_cursor;
+        self.models_cursor = (
+            row.saturating_add_signed(rows).min(max_row),
+            col.saturating_add_signed(cols).min(max_col),
+        );
+    }
+
+    /// Start a pull (model missing) or delete (model present) of the
+    /// selected Models tab cell, through the Dashboard confirmation
+    pub fn act_on_models_cell(&mut self) {
+        let models = self.known_models();
+        let workers = self.worker_names();
+        let (row, col) = self.models_cursor;
+        let (Some(model), Some(worker)) = (models.get(row), workers.get(col)) else { return };
+        let present = self.worker_tags.as_ref()
+            .and_then(|t| t.get(worker))
+            .is_some_and(|m| m.contains(model));
+        let action = if present { ActionType::Delete } else { ActionType::Pull };
+
+        self.selected_worker = col;
+        self.current_tab = Tab::Dashboard;
+        self.worker_detail = None;
+        self.action_panel_state = ActionPanelState::Confirmation(model.clone(), action);
+        self.action_target = ActionTarget::Selected;
+        self.focus = Focus::ActionPanelConfirm;
+        self.confirmation_selection = 0;
+        self.action_panel_scroll = 0;
+    }
+
+    /// Mark or unmark the highlighted worker for multi-worker actions
+    pub fn toggle_marked_worker(&mut self) {
+        let Some(name) = self.get_selected_worker_name() else { return };
+        if !self.marked_workers.remove(&name) {
+            self.marked_workers.insert(name);
+        }
+    }
+
+    /// Target of a freshly opened pull/delete: the marked workers if any
+    pub fn reset_action_target(&mut self) {
+        self.action_target = if self.marked_workers.is_empty() {
+            ActionTarget::Selected
+        } else {
+            ActionTarget::Marked
+        };
+    }
+
+    pub fn cycle_action_target(&mut self) {
+        self.action_target = self.action_target.next();
+    }
+
+    /// Workers `action_target` resolves to for `model`, sorted by name
+    pub fn target_workers(&self, model: &str, action_type: ActionType) -> Vec<String> {
+        let workers = self.worker_names();
+        match self.action_target {
+            ActionTarget::Selected => self.get_selected_worker_name().into_iter().collect(),
+            ActionTarget::Marked => workers.into_iter().filter(|w| self.marked_workers.contains(w)).collect(),
+            ActionTarget::All => workers,
+            ActionTarget::Eligible => workers.into_iter().filter(|w| {
+                let holds = self.worker_tags.as_ref()
+                    .and_then(|tags| tags.get(w))
+                    .is_some_and(|models| models.iter().any(|m| same_model(m, model)));
+                holds == (action_type == ActionType::Delete)
+            }).collect(),
+        }
+    }
+
+    /// Update one worker's row of the multi-worker action table
+    pub fn set_batch_state(&mut self, worker: &str, state: BatchState) {
+        if let Some(row) = self.batch.iter_mut().find(|r| r.worker == worker) {
+            row.state = state;
+        }
+    }
+
+}
+
+/// Whether tag `tag` names `model`, treating a missing tag as `:latest`
+fn same_model(tag: &str, model: &str) -> bool {
+    tag == model || (!model.contains(':') && tag.strip_suffix(":latest") == Some(model))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app() -> App {
+        let mut app = App::new(vec![Profile::single_port("lab", "http://hive.lab", 6668)]);
+        app.set_active_profile(0);
+        app
+    }
+
+    fn circuit_open(since: DateTime<Utc>) -> ClientError {
+        ClientError::CircuitOpen { endpoint: "worker/status".into(), since }
+    }
+
+    #[test]
+    fn an_outage_after_leaving_a_tab_shows_as_unreachable() {
+        let mut app = app();
+        app.current_tab = Tab::Keys;
+        app.apply_poll(PollData::Keys(Vec::new()));
+        app.current_tab = Tab::Dashboard;
+        let since = Utc::now();
+        for resource in Tab::Dashboard.resources() {
+            app.record_poll_error(*resource, &circuit_open(since));
+        }
+        assert_eq!(app.connection_status(), ConnectionStatus::Offline(since));
+
+        // Back on Keys only its own, still answering, endpoint counts
+        app.current_tab = Tab::Keys;
+        assert_eq!(app.connection_status(), ConnectionStatus::Online);
     }
 
+    #[test]
+    fn only_polled_endpoints_count_as_failing() {
+        let mut app = app();
+        app.current_tab = Tab::Keys;
+        app.record_poll_error(Resource::Keys, &ClientError::Server { status: 500, body: String::new() });
+        app.current_tab = Tab::Queues;
+        app.apply_poll(PollData::Queue(QueueMap::new()));
+        assert_eq!(app.connection_status(), ConnectionStatus::Online);
+
+        app.current_tab = Tab::Keys;
+        assert_eq!(app.connection_status(), ConnectionStatus::Failing(vec![Resource::Keys]));
+    }
+
+    #[test]
+    fn cancelling_a_chat_before_the_reply_drops_its_placeholder() {
+        let mut app = app();
+        app.conversation_mut().messages.extend([
+            ChatMessage { role: "user".into(), content: "Hi".into() },
+            ChatMessage { role: "assistant".into(), content: String::new() },
+        ]);
+        app.is_generating = true;
+        app.cancel_generation();
+        let roles: Vec<&str> = app.conversation().messages.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, ["user"]);
+    }
+
+    #[test]
+    fn cancelling_a_chat_keeps_a_partial_reply() {
+        let mut app = app();
+        app.conversation_mut().messages.push(ChatMessage { role: "assistant".into(), content: "Hel".into() });
+        app.is_generating = true;
+        app.cancel_generation();
+        assert_eq!(app.conversation().messages.len(), 1);
+    }
+
+    #[test]
+    fn without_polled_results_the_latest_ones_stand_in() {
+        let mut app = app();
+        assert_eq!(app.connection_status(), ConnectionStatus::Connecting);
+        app.apply_poll(PollData::Queue(QueueMap::new()));
+        app.current_tab = Tab::Fleet;
+        assert_eq!(app.connection_status(), ConnectionStatus::Online);
+    }
 }
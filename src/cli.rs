@@ -0,0 +1,539 @@
+// src/cli.rs
+use std::path::PathBuf;
+use std::time::Duration;
+
+use chrono::Utc;
+use clap::{Parser, Subcommand};
+use futures::StreamExt;
+use serde_json::{json, Value};
+
+use crate::clients::{infer_client::HiveInferClient, manage_client::HiveManageClient};
+use crate::config::Profile;
+use crate::errors::ClientError;
+use crate::exporter;
+use crate::models::{AuthKey, ClusterSummary, PullStatus};
+use crate::utils::ndjson;
+
+/// Exit code: command succeeded
+pub const EXIT_OK: i32 = 0;
+/// Exit code: HiveCore could not be reached or rejected the request
+pub const EXIT_API_ERROR: i32 = 1;
+/// Exit code: no usable profile (clap uses 2 for usage errors)
+pub const EXIT_CONFIG_ERROR: i32 = 3;
+/// Exit code: the request went through but the operation reported failure
+pub const EXIT_OPERATION_FAILED: i32 = 4;
+
+/// Terminal monitor and command line client for HiveCore
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// Profile name from profiles.toml (defaults to the first profile)
+    #[arg(long, short, global = true)]
+    pub profile: Option<String>,
+
+    /// Print JSON instead of human-readable tables
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    /// Seconds before a request to HiveCore times out
+    #[arg(long, global = true, default_value_t = 30, value_parser = clap::value_parser!(u64).range(1..))]
+    pub timeout: u64,
+
+    /// Retries of a failed read request, with exponential backoff
+    #[arg(long, global = true, default_value_t = 2)]
+    pub retries: u32,
+
+    /// Run against a built-in fake HiveCore instead of a configured profile
+    #[arg(long, global = true)]
+    pub demo: bool,
+
+    /// Keep metrics history on disk across sessions
+    #[arg(long)]
+    pub persist_history: bool,
+
+    /// Append every polled snapshot to this JSONL file
+    #[arg(long, value_name = "FILE")]
+    pub record: Option<PathBuf>,
+
+    /// Play a recorded session instead of polling HiveCore.
+    /// Keys: space play/pause, , and . seek 10s, < and > seek 1 min,
+    /// - and + change speed, Home and End jump to start and end.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["record", "demo", "persist_history"])]
+    pub replay: Option<PathBuf>,
+
+    /// Most workers a multi-worker pull or delete runs on at once
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u16).range(1..))]
+    pub parallel: u16,
+
+    /// Run a single command instead of the interactive TUI
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Commands run instead of the interactive TUI
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    #[command(flatten)]
+    Profile(ProfileCommand),
+    /// Poll in the background and serve Prometheus metrics on /metrics.
+    /// Every profile is exported unless --profile picks one.
+    ServeMetrics {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:9464")]
+        listen: String,
+        /// Seconds between polls
+        #[arg(long, default_value_t = 5)]
+        interval: u64,
+    },
+}
+
+/// Commands run against a single profile
+#[derive(Debug, Subcommand)]
+pub enum ProfileCommand {
+    /// Cluster summary: workers, busy workers and queue depth
+    Status,
+    /// Per-worker status, connections, last ping and versions
+    Workers,
+    /// Queue lengths per model and per node
+    Queue,
+    /// Manage authentication keys
+    Keys {
+        #[command(subcommand)]
+        action: KeysCommand,
+    },
+    /// Pull or delete models on workers
+    Model {
+        #[command(subcommand)]
+        action: ModelCommand,
+    },
+    /// Run a single non-streamed generation
+    Generate {
+        /// Model name, e.g. llama3:8b
+        model: String,
+        /// Prompt text
+        prompt: String,
+        /// Pin the request to a worker
+        #[arg(long)]
+        node: Option<String>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum KeysCommand {
+    /// List all keys
+    List {
+        /// Also print the secret key values
+        #[arg(long)]
+        show_values: bool,
+    },
+    /// Create a new key
+    Create {
+        /// Human-readable key name
+        name: String,
+        /// Key role, e.g. client or admin
+        #[arg(long, default_value = "client")]
+        role: String,
+        /// Also print the secret value of the new key
+        #[arg(long)]
+        show_values: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ModelCommand {
+    /// Pull a model onto a worker
+    Pull {
+        model: String,
+        /// Target worker
+        #[arg(long)]
+        node: Option<String>,
+    },
+    /// Delete a model from a worker
+    Delete {
+        model: String,
+        /// Target worker
+        #[arg(long)]
+        node: Option<String>,
+    },
+}
+
+/// Pick the profile named on the command line, or the first one
+pub fn select_profile(profiles: &[Profile], name: Option<&str>) -> Option<usize> {
+    match name {
+        Some(name) => profiles.iter().position(|p| p.name == name),
+        None => (!profiles.is_empty()).then_some(0),
+    }
+}
+
+/// Run a subcommand against `profile` and return the process exit code
+pub async fn run(command: ProfileCommand, profile: &Profile, json: bool) -> i32 {
+    match execute(command, profile, json).await {
+        Ok(code) => code,
+        Err(e) => {
+            if json {
+                println!("{}", json!({ "error": e.to_string(), "status": e.status() }));
+            } else {
+                eprintln!("Error: {}", e);
+            }
+            EXIT_API_ERROR
+        }
+    }
+}
+
+/// Run the metrics exporter for the named profile, or all of them
+pub async fn serve_metrics(profiles: Vec<Profile>, name: Option<&str>, listen: &str, interval: u64) -> i32 {
+    let profiles: Vec<Profile> = match name {
+        Some(name) => profiles.into_iter().filter(|p| p.name == name).collect(),
+        None => profiles,
+    };
+    if profiles.is_empty() {
+        eprintln!("Error: no matching HiveCore profiles to export");
+        return EXIT_CONFIG_ERROR;
+    }
+    match exporter::run(profiles, listen, Duration::from_secs(interval.max(1))).await {
+        Ok(()) => EXIT_OK,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            EXIT_API_ERROR
+        }
+    }
+}
+
+fn manage_client(profile: &Profile) -> Result<HiveManageClient, ClientError> {
+    HiveManageClient::new(
+        format!("{}:{}", profile.host, profile.port_manage),
+        &profile.admin_token,
+    )
+}
+
+fn infer_client(profile: &Profile) -> Result<HiveInferClient, ClientError> {
+    HiveInferClient::new(
+        format!("{}:{}", profile.host, profile.port_infer),
+        &profile.client_token,
+    )
+}
+
+async fn execute(command: ProfileCommand, profile: &Profile, json: bool) -> Result<i32, ClientError> {
+    match command {
+        ProfileCommand::Status => {
+            let client = manage_client(profile)?;
+            let statuses = client.get_worker_status().await?;
+            let connections = client.get_worker_connections().await?;
+            let queue = client.get_queue().await?;
+
+            let summary = ClusterSummary::from_data(&statuses, &connections, &queue);
+
+            if json {
+                println!("{}", json!({
+                    "profile": profile.name,
+                    "workers": summary.workers,
+                    "busy": summary.busy,
+                    "connections": summary.connections,
+                    "queued": summary.queued,
+                }));
+            } else {
+                print_table(
+                    &["Profile", "Workers", "Busy", "Connections", "Queued"],
+                    vec![vec![
+                        profile.name.clone(),
+                        summary.workers.to_string(),
+                        summary.busy.to_string(),
+                        summary.connections.to_string(),
+                        summary.queued.to_string(),
+                    ]],
+                );
+            }
+            Ok(EXIT_OK)
+        }
+        ProfileCommand::Workers => {
+            let client = manage_client(profile)?;
+            let statuses = client.get_worker_status().await?;
+            let connections = client.get_worker_connections().await?;
+            let pings = client.get_worker_pings().await?;
+            let versions = client.get_worker_versions().await?;
+            let tags = client.get_worker_tags().await?;
+
+            let mut names: Vec<&String> = statuses.keys().filter(|n| *n != "Unauthenticated").collect();
+            names.sort();
+            let rows: Vec<Value> = names.iter().map(|name| {
+                let last_ping = pings.get(*name).and_then(|t| t.last());
+                json!({
+                    "name": name,
+                    "status": statuses[*name].last(),
+                    "connections": connections.get(*name).copied().unwrap_or(0),
+                    "last_ping": last_ping.map(|t| t.to_rfc3339()),
+                    "last_ping_secs": last_ping.map(|t| (Utc::now() - *t).num_seconds()),
+                    "hive_version": versions.get(*name).map(|v| v.hive.clone()),
+                    "ollama_version": versions.get(*name).map(|v| v.ollama.clone()),
+                    "models": tags.get(*name).cloned().unwrap_or_default(),
+                })
+            }).collect();
+
+            if json {
+                println!("{}", Value::Array(rows));
+            } else {
+                let table = rows.iter().map(|w| vec![
+                    text(&w["name"]),
+                    text(&w["status"]),
+                    text(&w["connections"]),
+                    w["last_ping_secs"].as_i64().map(|s| format!("{}s ago", s)).unwrap_or_else(|| "-".into()),
+                    text(&w["hive_version"]),
+                    text(&w["ollama_version"]),
+                    w["models"].as_array().map(|m| m.len()).unwrap_or(0).to_string(),
+                ]).collect();
+                print_table(&["Name", "Status", "Conns", "Last Ping", "Hive", "Ollama", "Models"], table);
+            }
+            Ok(EXIT_OK)
+        }
+        ProfileCommand::Queue => {
+            let queue = manage_client(profile)?.get_queue().await?;
+            if json {
+                println!("{}", serde_json::to_string(&queue)?);
+            } else {
+                let mut rows: Vec<Vec<String>> = queue.iter()
+                    .map(|(name, count)| vec![name.clone(), count.to_string()])
+                    .collect();
+                rows.sort();
+                print_table(&["Queue", "Count"], rows);
+            }
+            Ok(EXIT_OK)
+        }
+        ProfileCommand::Keys { action } => {
+            let client = manage_client(profile)?;
+            let (keys, show_values) = match action {
+                KeysCommand::List { show_values } => (client.get_keys().await?, show_values),
+                KeysCommand::Create { name, role, show_values } => (client.create_key(&name, &role).await?, show_values),
+            };
+            println!("{}", keys_output(&keys, show_values, json)?);
+            Ok(EXIT_OK)
+        }
+        ProfileCommand::Model { action } => {
+            let client = infer_client(profile)?;
+            let resp = match &action {
+                ModelCommand::Pull { model, node } => client.pull_model_stream(model, node.as_deref()).await?,
+                ModelCommand::Delete { model, node } => client.delete_model_stream(model, node.as_deref()).await?,
+            };
+            print_progress(resp, json).await
+        }
+        ProfileCommand::Generate { model, prompt, node } => {
+            let resp = infer_client(profile)?
+                .generate(&model, &prompt, node.as_deref(), false)
+                .await?;
+            if json {
+                println!("{}", resp);
+            } else {
+                let output = resp.get("response")
+                    .or_else(|| resp.get("result"))
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+                    .unwrap_or_else(|| resp.to_string());
+                println!("{}", output);
+            }
+            Ok(EXIT_OK)
+        }
+    }
+}
+
+/// Keys as a table or JSON; values only with `show_values`, since they
+/// are credentials and should stay out of terminals and logs unless asked for
+fn keys_output(keys: &[AuthKey], show_values: bool, json: bool) -> Result<String, ClientError> {
+    if json && show_values {
+        return Ok(serde_json::to_string(keys)?);
+    }
+    if json {
+        let keys: Vec<Value> = keys.iter()
+            .map(|k| json!({ "id": k.id, "name": k.name, "role": k.role }))
+            .collect();
+        return Ok(Value::Array(keys).to_string());
+    }
+    let mut headers = vec!["ID", "Name", "Role"];
+    if show_values {
+        headers.push("Value");
+    }
+    let rows = keys.iter()
+        .map(|k| {
+            let mut row = vec![k.id.clone(), k.name.clone(), k.role.clone()];
+            if show_values {
+                row.push(k.value.clone());
+            }
+            row
+        })
+        .collect();
+    Ok(table(&headers, rows))
+}
+
+/// Echo a pull/delete NDJSON stream, line by line
+async fn print_progress(resp: reqwest::Response, json: bool) -> Result<i32, ClientError> {
+    let mut lines = Box::pin(ndjson::lines(resp.bytes_stream()));
+    let mut failed = false;
+
+    while let Some(line) = lines.next().await {
+        failed |= print_progress_line(&line?, json);
+    }
+
+    Ok(if failed { EXIT_OPERATION_FAILED } else { EXIT_OK })
+}
+
+/// Print one progress line; returns true if it reports a failure
+fn print_progress_line(line: &str, json: bool) -> bool {
+    if json {
+        println!("{}", line);
+    }
+    match ndjson::decode::<PullStatus>(line) {
+        Ok(status) => {
+            if !json {
+                println!("{}", if status.text().is_empty() { line } else { status.text() });
+            }
+            false
+        }
+        Err(ClientError::Stream(message)) => {
+            if !json {
+                eprintln!("error: {}", message);
+            }
+            true
+        }
+        Err(_) => {
+            if !json {
+                eprintln!("unexpected line: {}", line);
+            }
+            true
+        }
+    }
+}
+
+/// Render a JSON scalar for a table cell
+fn text(value: &Value) -> String {
+    match value {
+        Value::Null => "-".into(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Print rows as left-aligned, space-padded columns
+fn print_table(headers: &[&str], rows: Vec<Vec<String>>) {
+    println!("{}", table(headers, rows));
+}
+
+/// Rows as left-aligned, space-padded columns, one line each
+fn table(headers: &[&str], rows: Vec<Vec<String>>) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+    for row in &rows {
+        for (i, cell) in row.iter().enumerate() {
+            widths[i] = widths[i].max(cell.chars().count());
+        }
+    }
+    let format_row = |cells: Vec<String>| {
+        cells.iter()
+            .zip(&widths)
+            .map(|(c, w)| format!("{:<w$}", c, w = *w))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+    let mut lines = vec![format_row(headers.iter().map(|h| h.to_string()).collect())];
+    lines.extend(rows.into_iter().map(format_row));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::error::ErrorKind;
+    use serde_json::json;
+
+    use crate::mock::{MockHive, Reply, ADMIN_TOKEN, CLIENT_TOKEN};
+
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(std::iter::once("hive_monitor_tui").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn parses_profile_commands_with_global_flags_after_them() {
+        let cli = parse(&["keys", "create", "ci", "--show-values", "--json", "-p", "lab"]).unwrap();
+        assert!(cli.json);
+        assert_eq!(cli.profile.as_deref(), Some("lab"));
+        let Some(Command::Profile(ProfileCommand::Keys { action: KeysCommand::Create { name, role, show_values } })) = cli.command else {
+            panic!("unexpected command {:?}", cli.command);
+        };
+        assert_eq!((name.as_str(), role.as_str(), show_values), ("ci", "client", true));
+
+        let cli = parse(&["keys", "list"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Profile(ProfileCommand::Keys { action: KeysCommand::List { show_values: false } })),
+        ));
+    }
+
+    #[test]
+    fn parses_serve_metrics_with_its_defaults() {
+        let cli = parse(&["serve-metrics"]).unwrap();
+        let Some(Command::ServeMetrics { listen, interval }) = cli.command else {
+            panic!("unexpected command {:?}", cli.command);
+        };
+        assert_eq!((listen.as_str(), interval), ("127.0.0.1:9464", 5));
+    }
+
+    #[test]
+    fn without_a_command_the_tui_runs() {
+        let cli = parse(&[]).unwrap();
+        assert!(cli.command.is_none());
+        assert_eq!((cli.timeout, cli.retries, cli.parallel), (30, 2, 4));
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        assert_eq!(parse(&["--timeout", "0", "status"]).unwrap_err().kind(), ErrorKind::ValueValidation);
+        assert_eq!(parse(&["--parallel", "0"]).unwrap_err().kind(), ErrorKind::ValueValidation);
+        assert_eq!(parse(&["--replay", "s.jsonl", "--demo"]).unwrap_err().kind(), ErrorKind::ArgumentConflict);
+        assert_eq!(parse(&["model", "pull"]).unwrap_err().kind(), ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn picks_the_named_or_first_profile() {
+        let profiles = [Profile::single_port("lab", "http://lab", 1), Profile::single_port("prod", "http://prod", 1)];
+        assert_eq!(select_profile(&profiles, None), Some(0));
+        assert_eq!(select_profile(&profiles, Some("prod")), Some(1));
+        assert_eq!(select_profile(&profiles, Some("staging")), None);
+        assert_eq!(select_profile(&[], None), None);
+    }
+
+    #[test]
+    fn progress_lines_report_failures() {
+        assert!(!print_progress_line(r#"{"status":"pulling manifest"}"#, false));
+        assert!(print_progress_line(r#"{"error":"disk full"}"#, false));
+        assert!(print_progress_line("not json", true));
+    }
+
+    #[tokio::test]
+    async fn keys_leave_out_values_unless_asked_for() {
+        let hive = MockHive::start().await.unwrap();
+        let keys = manage_client(&hive.profile("mock")).unwrap().get_keys().await.unwrap();
+        for json in [false, true] {
+            let output = keys_output(&keys, false, json).unwrap();
+            assert!(output.contains("admin"), "{}", output);
+            assert!(!output.contains(ADMIN_TOKEN) && !output.contains(CLIENT_TOKEN), "{}", output);
+            let output = keys_output(&keys, true, json).unwrap();
+            assert!(output.contains(ADMIN_TOKEN) && output.contains(CLIENT_TOKEN), "{}", output);
+        }
+        let table = keys_output(&keys, false, false).unwrap();
+        assert_eq!(table.lines().next(), Some("ID  Name    Role"));
+    }
+
+    #[tokio::test]
+    async fn exit_codes_tell_api_errors_from_failed_operations() {
+        let hive = MockHive::start().await.unwrap();
+        let profile = hive.profile("mock");
+        let list = || ProfileCommand::Keys { action: KeysCommand::List { show_values: false } };
+        assert_eq!(run(list(), &profile, true).await, EXIT_OK);
+
+        hive.script("POST", "/api/pull", [Reply::ndjson(&[json!({ "status": "pulling" }), json!({ "error": "disk full" })])]);
+        let pull = ProfileCommand::Model { action: ModelCommand::Pull { model: "phi3".into(), node: None } };
+        assert_eq!(run(pull, &profile, true).await, EXIT_OPERATION_FAILED);
+
+        let wrong_token = profile.clone().with_tokens(CLIENT_TOKEN, "wrong");
+        assert_eq!(run(list(), &wrong_token, true).await, EXIT_API_ERROR);
+    }
+}
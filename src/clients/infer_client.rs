@@ -2,10 +2,9 @@
 
 use futures::StreamExt;
 use reqwest::{header::{HeaderMap, HeaderValue, AUTHORIZATION}};
-use tokio::sync::Mutex;
-use crate::{app::App, errors::ClientError, utils::http::HttpClient};
+use tokio::sync::mpsc;
+use crate::{errors::ClientError, models::{ActionEvent, ChatRequest, PullStatus}, utils::{http::HttpClient, ndjson}};
 use serde_json::Value;
-use std::{sync::Arc, time::Duration};
 
 pub struct HiveInferClient {
     client: HttpClient,
@@ -17,7 +16,7 @@ impl HiveInferClient {
         let mut auth_header = HeaderValue::from_str(&format!("Bearer {}", client_token))?;
         auth_header.set_sensitive(true);
         
-        let client = HttpClient::new(base_url.into(), client_token)?;
+        let client = HttpClient::new(base_url.into(), client_token)?.with_token_name("client token");
         Ok(HiveInferClient {
             client,
             auth_header,
@@ -41,217 +40,51 @@ impl HiveInferClient {
         node: Option<&str>,
         stream: bool,
     ) -> Result<Value, ClientError> {
-        let url = format!("{}/api/generate", self.client.base_url.trim_end_matches('/'));
         let headers = self.make_headers(node)?;
         let body = serde_json::json!({
             "model": model,
             "prompt": prompt,
             "stream": stream
         });
-        let resp = self.client.post(&url, &body, Some(headers)).await?;
+        let resp = self.client.post("api/generate", &body, Some(headers)).await?;
         Ok(resp)
     }
 
     /// List all models (tags) available on the worker
     pub async fn list_models(&self, node: Option<&str>) -> Result<Vec<String>, ClientError> {
-        let url = format!("{}/api/models", self.client.base_url.trim_end_matches('/'));
         let headers = self.make_headers(node)?;
-        let resp = self.client.get(&url, Some(headers)).await?;
+        let resp = self.client.get("api/models", Some(headers)).await?;
         Ok(resp)
     }
 
-    /// Pull a model onto the worker
-    ///
-    /// POST /api/pull with body `{ "name": "<model>" }`
-    /// This method will now stream JSON lines and update the App state.
-    pub async fn pull_model(&self, model: &str, node: Option<&str>, app_arc: Arc<Mutex<App>>) -> Result<(), ClientError> {
-        let url = format!("{}/api/pull", self.client.base_url.trim_end_matches('/'));
+    /// Start a pull and return the raw NDJSON progress response
+    pub async fn pull_model_stream(&self, model: &str, node: Option<&str>) -> Result<reqwest::Response, ClientError> {
         let headers = self.make_headers(node)?;
         let body = serde_json::json!({ "name": model });
-        let resp = self.client
-            .post_raw(&url, &body, Some(headers))
-            .await?;
-
-        // Get the byte stream from the response
-        let mut byte_stream = resp.bytes_stream(); // This exists!
-        let mut buffer = Vec::new(); // Buffer for incomplete lines
-        let mut overall_success = true;
-
-        while let Some(chunk_result) = byte_stream.next().await {
-            let chunk = chunk_result?; // Propagate reqwest::Error
-
-            buffer.extend_from_slice(&chunk); // Add chunk to buffer
-
-            // Process lines from the buffer
-            while let Some(newline_pos) = buffer.iter().position(|&b| b == b'\n') {
-                let line_bytes = buffer.drain(..=newline_pos).collect::<Vec<u8>>();
-                let trimmed_line = String::from_utf8(line_bytes) // Convert to String
-                    .map_err(ClientError::Decode)?
-                    .trim()
-                    .to_string(); // Trim and own the string
-
-                if trimmed_line.is_empty() {
-                    continue; // Skip empty lines
-                }
-
-                // Attempt to parse each line as JSON
-                match serde_json::from_str::<Value>(&trimmed_line) {
-                    Ok(json_value) => {
-                        let message_val = json_value.get("status").or(json_value.get("message"));
-                        let message = message_val
-                            .and_then(|v| v.as_str())
-                            .map(|s| s.to_string())
-                            .unwrap_or_else(|| json_value.to_string());
-
-                        // Assuming "error" field indicates failure if present and not null/false
-                        let is_line_success = json_value.get("error").is_none_or(|err| err.is_null() || !err.as_bool().unwrap_or(false));
-
-                        if !is_line_success {
-                            overall_success = false;
-                            app_arc.lock().await.add_banner(format!("Pull Error: {}", message));
-                        }
-                        app_arc.lock().await.add_action_output_line(message, is_line_success);
-                    },
-                    Err(e) => {
-                        overall_success = false;
-                        let error_msg = format!("Non-JSON line: {} (Parse Error: {})", trimmed_line, e);
-                        app_arc.lock().await.add_action_output_line(error_msg.clone(), false);
-                        app_arc.lock().await.add_banner(error_msg);
-                    }
-                }
-            }
-        }
-
-        // Process any remaining data in the buffer after the stream ends (e.g., last line without newline)
-        if !buffer.is_empty() {
-            let remaining_line = String::from_utf8(buffer)
-                .map_err(ClientError::Decode)?
-                .trim()
-                .to_string();
-            if !remaining_line.is_empty() {
-                match serde_json::from_str::<Value>(&remaining_line) {
-                    Ok(json_value) => {
-                        let message_val = json_value.get("status").or(json_value.get("message"));
-                        let message = message_val
-                            .and_then(|v| v.as_str())
-                            .map(|s| s.to_string())
-                            .unwrap_or_else(|| json_value.to_string());
-                        let is_line_success = json_value.get("error").is_none_or(|err| err.is_null() || !err.as_bool().unwrap_or(false));
-                        if !is_line_success { overall_success = false; }
-                        app_arc.lock().await.add_action_output_line(message, is_line_success);
-                    },
-                    Err(e) => {
-                        overall_success = false;
-                        let error_msg = format!("Non-JSON final line: {} (Parse Error: {})", remaining_line, e);
-                        app_arc.lock().await.add_action_output_line(error_msg.clone(), false);
-                        app_arc.lock().await.add_banner(error_msg);
-                    }
-                }
-            }
-        }
-
-        // Final status message
-        let final_message = if overall_success {
-            "Model pull completed successfully.".to_string()
-        } else {
-            "Model pull completed with errors.".to_string()
-        };
-        app_arc.lock().await.add_action_output_line(final_message, overall_success);
-        Ok(())
+        self.client.post_raw("api/pull", &body, Some(headers)).await
     }
 
-    pub async fn delete_model(&self, model: &str, node: Option<&str>, app_arc: Arc<Mutex<App>>) -> Result<(), ClientError> {
-        let url = format!("{}/api/delete", self.client.base_url.trim_end_matches('/'));
+    /// Start a delete and return the raw NDJSON response
+    pub async fn delete_model_stream(&self, model: &str, node: Option<&str>) -> Result<reqwest::Response, ClientError> {
         let headers = self.make_headers(node)?;
         let body = serde_json::json!({ "name": model });
+        self.client.delete_raw("api/delete", &body, Some(headers)).await
+    }
 
-        let resp = self.client
-            .delete_raw(&url, &body, Some(headers))
-            .await?;
-
-        let mut byte_stream = resp.bytes_stream();
-        let mut buffer = Vec::new();
-        let mut overall_success = true;
-
-        while let Some(chunk_result) = byte_stream.next().await {
-            let chunk = chunk_result?;
-
-            buffer.extend_from_slice(&chunk);
-
-            while let Some(newline_pos) = buffer.iter().position(|&b| b == b'\n') {
-                let line_bytes = buffer.drain(..=newline_pos).collect::<Vec<u8>>();
-                let trimmed_line = String::from_utf8(line_bytes)
-                    .map_err(ClientError::Decode)?
-                    .trim()
-                    .to_string();
-
-                if trimmed_line.is_empty() {
-                    continue;
-                }
-
-                match serde_json::from_str::<Value>(&trimmed_line) {
-                    Ok(json_value) => {
-                        let message_val = json_value.get("status").or(json_value.get("message"));
-                        let message = message_val
-                            .and_then(|v| v.as_str())
-                            .map(|s| s.to_string())
-                            .unwrap_or_else(|| json_value.to_string());
-
-                        let is_line_success = json_value.get("error").is_none_or(|err| err.is_null() || !err.as_bool().unwrap_or(false));
-
-                        if !is_line_success {
-                            overall_success = false;
-                            app_arc.lock().await.add_banner(format!("Delete Error: {}", message));
-                        }
-                        app_arc.lock().await.add_action_output_line(message, is_line_success);
-                    },
-                    Err(e) => {
-                        overall_success = false;
-                        let error_msg = format!("Non-JSON line: {} (Parse Error: {})", trimmed_line, e);
-                        app_arc.lock().await.add_action_output_line(error_msg.clone(), false);
-                        app_arc.lock().await.add_banner(error_msg);
-                    }
-                }
-            }
-        }
-
-        if !buffer.is_empty() {
-            let remaining_line = String::from_utf8(buffer)
-                .map_err(ClientError::Decode)?
-                .trim()
-                .to_string();
-            if !remaining_line.is_empty() {
-                match serde_json::from_str::<Value>(&remaining_line) {
-                    Ok(json_value) => {
-                        let message_val = json_value.get("status").or(json_value.get("message"));
-                        let message = message_val
-                            .and_then(|v| v.as_str())
-                            .map(|s| s.to_string())
-                            .unwrap_or_else(|| json_value.to_string());
-                        let is_line_success = json_value.get("error").is_none_or(|err| err.is_null() || !err.as_bool().unwrap_or(false));
-                        if !is_line_success { overall_success = false; }
-                        app_arc.lock().await.add_action_output_line(message, is_line_success);
-                    },
-                    Err(e) => {
-                        overall_success = false;
-                        let error_msg = format!("Non-JSON final line: {} (Parse Error: {})", remaining_line, e);
-                        app_arc.lock().await.add_action_output_line(error_msg.clone(), false);
-                        app_arc.lock().await.add_banner(error_msg);
-                    }
-                }
-            }
-        }
+    /// Pull a model onto the worker
+    ///
+    /// POST /api/pull with body `{ "name": "<model>" }`, reporting each
+    /// line of the progress stream on `events`
+    pub async fn pull_model(&self, model: &str, node: Option<&str>, events: mpsc::Sender<ActionEvent>) -> Result<(), ClientError> {
+        let resp = self.pull_model_stream(model, node).await?;
+        report_progress(resp, &events).await
+    }
 
-        let final_message = if overall_success {
-            "Model delete completed successfully.".to_string()
-        } else {
-            "Model delete completed with errors.".to_string()
-        };
-        app_arc.lock().await.add_action_output_line(final_message, overall_success);
-        Ok(())
+    /// Delete a model from the worker, reporting progress on `events`
+    pub async fn delete_model(&self, model: &str, node: Option<&str>, events: mpsc::Sender<ActionEvent>) -> Result<(), ClientError> {
+        let resp = self.delete_model_stream(model, node).await?;
+        report_progress(resp, &events).await
     }
-    
-    
 
     /// (Optional) Streamed generate: returns a reqwest `Response` that you can
     /// `.bytes_stream()` and parse chunked JSON. E.g. for `/api/generate?stream=true`
@@ -261,18 +94,211 @@ impl HiveInferClient {
         prompt: &str,
         node: Option<&str>,
     ) -> Result<reqwest::Response, ClientError> {
-        let url = format!(
-            "{}/api/generate?stream=true",
-            self.client.base_url.trim_end_matches('/')
-        );
         let headers = self.make_headers(node)?;
         let body = serde_json::json!({
             "model": model,
             "prompt": prompt,
+            "stream": true,
         });
         let resp = self.client
-            .post_raw(&url, &body, Some(headers))
+            .post_raw("api/generate?stream=true", &body, Some(headers))
+            .await?;
+        Ok(resp)
+    }
+
+    /// Streamed chat: returns the raw NDJSON response, one chunk of the
+    /// assistant message per line.
+    pub async fn chat_stream(
+        &self,
+        request: &ChatRequest,
+        node: Option<&str>,
+    ) -> Result<reqwest::Response, ClientError> {
+        let headers = self.make_headers(node)?;
+        let body = ChatRequest { stream: true, ..request.clone() };
+        let resp = self.client
+            .post_raw("api/chat", &body, Some(headers))
             .await?;
         Ok(resp)
     }
 }
+
+/// Turn a pull/delete NDJSON stream into action events. Error lines mark
+/// the action failed but reading continues; transport errors end it.
+async fn report_progress(resp: reqwest::Response, events: &mpsc::Sender<ActionEvent>) -> Result<(), ClientError> {
+    let mut lines = Box::pin(ndjson::events::<PullStatus>(resp));
+    let mut success = true;
+
+    while let Some(line) = lines.next().await {
+        let event = match line {
+            Ok(line) if line.digest.is_some() && line.total.is_some() => ActionEvent::Progress(line),
+            Ok(line) => ActionEvent::Status(line.text().to_string()),
+            Err(ClientError::Stream(message)) => {
+                success = false;
+                ActionEvent::LineError(message)
+            }
+            Err(e @ (ClientError::Json(_) | ClientError::Decode(_))) => {
+                success = false;
+                ActionEvent::LineError(format!("Malformed line: {}", e))
+            }
+            Err(e) => return Err(e),
+        };
+        // A closed receiver only means nobody is watching any more
+        let _ = events.send(event).await;
+    }
+    let _ = events.send(ActionEvent::Finished { success }).await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::mock::{MockHive, Reply, ADMIN_TOKEN, CLIENT_TOKEN};
+
+    /// Run `pull_model` and collect every event it reports
+    async fn pull(client: &HiveInferClient, model: &str, node: Option<&str>) -> (Result<(), ClientError>, Vec<ActionEvent>) {
+        let (tx, mut rx) = mpsc::channel(8);
+        let collect = async {
+            let mut events = Vec::new();
+            while let Some(event) = rx.recv().await {
+                events.push(event);
+            }
+            events
+        };
+        tokio::join!(client.pull_model(model, node, tx), collect)
+    }
+
+    fn finished(events: &[ActionEvent]) -> Option<bool> {
+        match events.last() {
+            Some(ActionEvent::Finished { success }) => Some(*success),
+            _ => None,
+        }
+    }
+
+    #[tokio::test]
+    async fn pull_reports_status_and_layer_progress() {
+        let hive = MockHive::start().await.unwrap();
+        let client = HiveInferClient::new(hive.url(), CLIENT_TOKEN).unwrap();
+
+        let (result, events) = pull(&client, "phi3", Some("cpu-01")).await;
+        result.unwrap();
+        assert!(matches!(&events[0], ActionEvent::Status(s) if s == "pulling manifest"));
+        assert_eq!(events.iter().filter(|e| matches!(e, ActionEvent::Progress(_))).count(), 10);
+        assert!(matches!(&events[events.len() - 2], ActionEvent::Status(s) if s == "success"));
+        assert_eq!(finished(&events), Some(true));
+
+        let request = hive.requests().pop().unwrap();
+        assert_eq!(request.header("node"), Some("cpu-01"));
+        assert_eq!(request.header("authorization"), Some(format!("Bearer {}", CLIENT_TOKEN).as_str()));
+        assert!(hive.models_on("cpu-01").contains(&"phi3".to_string()));
+        assert!(!hive.models_on("gpu-01").contains(&"phi3".to_string()));
+    }
+
+    #[tokio::test]
+    async fn error_lines_fail_the_pull_but_keep_reading() {
+        let hive = MockHive::start().await.unwrap();
+        hive.script("POST", "/api/pull", [Reply::ndjson(&[
+            json!({ "status": "pulling manifest" }),
+            json!({ "error": "pull model manifest: file does not exist" }),
+            json!({ "status": "cleaning up" }),
+        ])]);
+        let client = HiveInferClient::new(hive.url(), CLIENT_TOKEN).unwrap();
+
+        let (result, events) = pull(&client, "nope", None).await;
+        result.unwrap();
+        assert!(matches!(&events[1], ActionEvent::LineError(m) if m == "pull model manifest: file does not exist"));
+        assert!(matches!(&events[2], ActionEvent::Status(s) if s == "cleaning up"));
+        assert_eq!(finished(&events), Some(false));
+    }
+
+    #[tokio::test]
+    async fn malformed_lines_are_reported_as_line_errors() {
+        let hive = MockHive::start().await.unwrap();
+        hive.script("POST", "/api/pull", [Reply::raw(200, "application/x-ndjson", &b"{\"status\":\"pulling manifest\"}\n{\"status\": \n\xff\n{\"status\":\"success\"}\n"[..])]);
+        let client = HiveInferClient::new(hive.url(), CLIENT_TOKEN).unwrap();
+
+        let (result, events) = pull(&client, "phi3", None).await;
+        result.unwrap();
+        let errors = events.iter().filter(|e| matches!(e, ActionEvent::LineError(m) if m.starts_with("Malformed line"))).count();
+        assert_eq!(errors, 2);
+        assert!(matches!(&events[3], ActionEvent::Status(s) if s == "success"));
+        assert_eq!(finished(&events), Some(false));
+    }
+
+    #[tokio::test]
+    async fn slow_streams_arrive_line_by_line() {
+        let hive = MockHive::start().await.unwrap();
+        let lines: Vec<_> = ["pulling manifest", "verifying sha256 digest", "success"].iter()
+            .map(|s| json!({ "status": s }))
+            .collect();
+        hive.script("POST", "/api/pull", [Reply::ndjson(&lines).paced(Duration::from_millis(150))]);
+        let client = HiveInferClient::new(hive.url(), CLIENT_TOKEN).unwrap();
+
+        let started = std::time::Instant::now();
+        let (result, events) = pull(&client, "phi3", None).await;
+        result.unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(300));
+        assert_eq!(events.len(), 4);
+        assert_eq!(finished(&events), Some(true));
+    }
+
+    #[tokio::test]
+    async fn deleting_a_missing_model_is_not_found() {
+        let hive = MockHive::start().await.unwrap();
+        let client = HiveInferClient::new(hive.url(), CLIENT_TOKEN).unwrap();
+        let (tx, _rx) = mpsc::channel(8);
+
+        let err = client.delete_model("phi3", Some("gpu-02"), tx).await.unwrap_err();
+        assert!(matches!(err, ClientError::NotFound { ref body, .. } if body.contains("model 'phi3' not found")));
+    }
+
+    #[tokio::test]
+    async fn delete_removes_the_model_from_the_worker() {
+        let hive = MockHive::start().await.unwrap();
+        let client = HiveInferClient::new(hive.url(), ADMIN_TOKEN).unwrap();
+        let (tx, mut rx) = mpsc::channel(8);
+
+        client.delete_model("llama3.1:8b", Some("gpu-02"), tx).await.unwrap();
+        assert!(matches!(rx.recv().await, Some(ActionEvent::Status(s)) if s == "success"));
+        assert!(hive.models_on("gpu-02").is_empty());
+        assert_eq!(hive.models_on("gpu-01").len(), 2);
+    }
+
+    #[tokio::test]
+    async fn generate_and_list_models() {
+        let hive = MockHive::start().await.unwrap();
+        let client = HiveInferClient::new(hive.url(), CLIENT_TOKEN).unwrap();
+
+        let reply = client.generate("qwen2.5:7b", "hello", None, false).await.unwrap();
+        assert!(reply["response"].as_str().unwrap().ends_with("answering: hello"));
+        assert_eq!(reply["done"], json!(true));
+
+        let models = client.list_models(None).await.unwrap();
+        assert_eq!(models, vec!["llama3.1:8b", "nomic-embed-text:latest", "qwen2.5:7b"]);
+    }
+
+    #[tokio::test]
+    async fn generate_stream_ends_with_counts() {
+        let hive = MockHive::start().await.unwrap();
+        let client = HiveInferClient::new(hive.url(), CLIENT_TOKEN).unwrap();
+
+        let resp = client.generate_stream("llama3.1:8b", "hi", Some("gpu-01")).await.unwrap();
+        let chunks: Vec<Value> = ndjson::events(resp).map(Result::unwrap).collect().await;
+        let text: String = chunks.iter().filter_map(|c| c["response"].as_str()).collect();
+        assert!(text.ends_with("answering: hi"));
+        assert_eq!(chunks.last().unwrap()["done"], json!(true));
+        assert!(chunks.last().unwrap()["eval_count"].as_u64().unwrap() > 0);
+    }
+
+    #[tokio::test]
+    async fn rejected_token_names_the_client_token() {
+        let hive = MockHive::start().await.unwrap();
+        let client = HiveInferClient::new(hive.url(), "wrong").unwrap();
+
+        let err = client.list_models(None).await.unwrap_err();
+        assert!(matches!(err, ClientError::Unauthorized { token: "client token", .. }));
+    }
+}
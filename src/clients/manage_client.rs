@@ -11,7 +11,7 @@ pub struct HiveManageClient {
 impl HiveManageClient {
     /// Create a new management client
     pub fn new(base_url: impl Into<String>, admin_token: &str) -> Result<Self, ClientError> {
-        let http = HttpClient::new(base_url, admin_token)?;
+        let http = HttpClient::new(base_url, admin_token)?.with_token_name("admin token");
         Ok(HiveManageClient { http })
     }
 
@@ -64,3 +64,139 @@ impl HiveManageClient {
         Ok(crate::utils::parsing::parse_auth_keys(raw))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::mock::{MockHive, Reply, ADMIN_TOKEN, CLIENT_TOKEN};
+
+    async fn client(hive: &MockHive) -> HiveManageClient {
+        HiveManageClient::new(hive.url(), ADMIN_TOKEN).unwrap()
+    }
+
+    #[tokio::test]
+    async fn reads_every_management_endpoint() {
+        let hive = MockHive::start().await.unwrap();
+        let client = client(&hive).await;
+
+        assert_eq!(client.get_worker_status().await.unwrap()["gpu-01"], vec!["Polling", "Working"]);
+        assert_eq!(client.get_worker_connections().await.unwrap()["gpu-02"], 1);
+        assert_eq!(client.get_worker_versions().await.unwrap()["cpu-01"].ollama, "0.6.5");
+        assert_eq!(client.get_worker_tags().await.unwrap()["gpu-02"], vec!["llama3.1:8b"]);
+        assert_eq!(client.get_queue().await.unwrap()["Model: llama3.1:8b"], 2);
+        assert_eq!(client.get_worker_pings().await.unwrap()["gpu-01"].len(), 6);
+        assert_eq!(client.get_keys().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn create_key_posts_name_and_role() {
+        let hive = MockHive::start().await.unwrap();
+        let keys = client(&hive).await.create_key("ci", "Client").await.unwrap();
+
+        assert!(keys.iter().any(|k| k.name == "ci" && k.role == "Client"));
+        let request = hive.requests().into_iter().find(|r| r.method == "POST").unwrap();
+        assert_eq!(request.path, "/key");
+        assert_eq!(serde_json::from_slice::<serde_json::Value>(&request.body).unwrap(), json!({ "name": "ci", "role": "Client" }));
+    }
+
+    #[tokio::test]
+    async fn rejected_token_names_the_admin_token() {
+        let hive = MockHive::start().await.unwrap();
+        let client = HiveManageClient::new(hive.url(), CLIENT_TOKEN).unwrap();
+
+        let err = client.get_queue().await.unwrap_err();
+        assert!(matches!(err, ClientError::Unauthorized { status: 401, token: "admin token", .. }));
+        assert!(err.to_string().contains("Invalid or missing token"));
+    }
+
+    #[tokio::test]
+    async fn server_error_keeps_the_body() {
+        let hive = MockHive::start().await.unwrap();
+        hive.script("GET", "/worker/tags", [Reply::status(500, "database is locked")]);
+
+        let err = client(&hive).await.get_worker_tags().await.unwrap_err();
+        assert!(matches!(err, ClientError::Server { status: 500, ref body } if body == "database is locked"));
+    }
+
+    #[tokio::test]
+    async fn retries_reads_after_transient_failures() {
+        let hive = MockHive::start().await.unwrap();
+        hive.script("GET", "/queue", [
+            Reply::status(503, "starting"),
+            Reply::status(503, "starting"),
+            Reply::json(json!({ "Model: phi3": 4 })),
+        ]);
+
+        let queue = client(&hive).await.get_queue().await.unwrap();
+        assert_eq!(queue["Model: phi3"], 4);
+        assert_eq!(hive.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_writes() {
+        let hive = MockHive::start().await.unwrap();
+        hive.script("POST", "/key", [Reply::status(503, "starting")]);
+
+        assert!(client(&hive).await.create_key("ci", "Client").await.is_err());
+        assert_eq!(hive.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn opens_the_circuit_after_repeated_failures() {
+        let hive = MockHive::start().await.unwrap();
+        hive.script("GET", "/worker/status", [Reply::status(502, "bad gateway")]);
+        let client = client(&hive).await;
+
+        for _ in 0..3 {
+            assert!(matches!(client.get_worker_status().await, Err(ClientError::Server { .. })));
+        }
+        let sent = hive.requests().len();
+        assert!(matches!(client.get_worker_status().await, Err(ClientError::CircuitOpen { .. })));
+        assert_eq!(hive.requests().len(), sent);
+        // Other endpoints have their own circuit
+        assert!(client.get_queue().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn waits_for_a_slow_answer() {
+        let hive = MockHive::start().await.unwrap();
+        hive.script("GET", "/worker/versions", [
+            Reply::json(json!({ "gpu-01": { "hive": "0.5.0", "ollama": "0.7.0" } })).delayed(Duration::from_millis(500)),
+        ]);
+
+        let versions = client(&hive).await.get_worker_versions().await.unwrap();
+        assert_eq!(versions["gpu-01"].hive, "0.5.0");
+    }
+
+    #[tokio::test]
+    async fn malformed_bodies_fail_without_retrying() {
+        let hive = MockHive::start().await.unwrap();
+        hive.script("GET", "/queue", [Reply::raw(200, "application/json", "{\"Model: phi3\": ")]);
+        hive.script("GET", "/worker/status", [Reply::json(json!(["gpu-01"]))]);
+        let client = client(&hive).await;
+
+        assert!(matches!(client.get_queue().await, Err(ClientError::Reqwest(ref e)) if e.is_decode()));
+        assert!(matches!(client.get_worker_status().await, Err(ClientError::Json(_))));
+        assert_eq!(hive.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn lenient_parsers_skip_what_they_cannot_read() {
+        let hive = MockHive::start().await.unwrap();
+        hive.script("GET", "/worker/pings", [Reply::json(json!({
+            "gpu-01": ["2026-10-16T10:00:00Z", "yesterday", 42],
+            "gpu-02": "not a list",
+        }))]);
+        hive.script("GET", "/key", [Reply::json(json!(3))]);
+        let client = client(&hive).await;
+
+        let pings = client.get_worker_pings().await.unwrap();
+        assert_eq!(pings["gpu-01"].len(), 1);
+        assert!(pings["gpu-02"].is_empty());
+        assert!(client.get_keys().await.unwrap().is_empty());
+    }
+}
@@ -4,7 +4,7 @@ use directories::ProjectDirs;
 use crate::errors::ClientError;
 
 /// Representation of a single HiveCore profile
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct Profile {
     pub name: String,
     pub host: String,
@@ -14,22 +14,53 @@ pub struct Profile {
     pub admin_token: String,
 }
 
+impl Profile {
+    /// A profile serving both APIs on `port` of `host`, without tokens; the
+    /// demo, replays and tests build theirs from it
+    pub fn single_port(name: &str, host: &str, port: u16) -> Self {
+        Profile {
+            name: name.to_string(),
+            host: host.to_string(),
+            port_infer: port,
+            port_manage: port,
+            client_token: String::new(),
+            admin_token: String::new(),
+        }
+    }
+
+    pub fn with_tokens(mut self, client_token: &str, admin_token: &str) -> Self {
+        self.client_token = client_token.to_string();
+        self.admin_token = admin_token.to_string();
+        self
+    }
+}
+
 /// Wrapper for the profiles file
 #[derive(Debug, Serialize, Deserialize)]
 struct ProfilesFile {
     pub profiles: Vec<Profile>,
 }
 
-/// Returns the path to the profiles.toml file, creating directories if needed
-fn profiles_path() -> Result<PathBuf, ClientError> {
-    let proj = ProjectDirs::from("si", "famnit", "hivecore-tui")
+/// Returns the platform's config and data directories of the app
+pub fn project_dirs() -> Result<ProjectDirs, ClientError> {
+    ProjectDirs::from("si", "famnit", "hivecore-tui")
         .ok_or_else(|| ClientError::Io(std::io::Error::new(
             std::io::ErrorKind::NotFound,
-            "Unable to determine config directory",
-        )))?;
+            "Unable to determine the app directories",
+        )))
+}
+
+/// Returns the path to a file in the config directory, creating directories if needed
+pub fn config_path(file: &str) -> Result<PathBuf, ClientError> {
+    let proj = project_dirs()?;
     let dir = proj.config_dir();
     fs::create_dir_all(dir)?;
-    Ok(dir.join("profiles.toml"))
+    Ok(dir.join(file))
+}
+
+/// Returns the path to the profiles.toml file
+fn profiles_path() -> Result<PathBuf, ClientError> {
+    config_path("profiles.toml")
 }
 
 /// Load all profiles from disk
@@ -1,10 +1,11 @@
 use std::{error::Error as StdError, fmt, string::FromUtf8Error};
+use chrono::{DateTime, Local, Utc};
 use reqwest::header::InvalidHeaderValue;
 
 /// Errors returned by HTTP client operations
 #[derive(Debug)]
 pub enum ClientError {
-    /// HTTP transport or status error
+    /// HTTP transport error
     Reqwest(reqwest::Error),
     /// Invalid header value (e.g., Authorization)
     HeaderInvalid(InvalidHeaderValue),
@@ -15,6 +16,72 @@ pub enum ClientError {
     /// I/O error (e.g., reading config)
     Io(std::io::Error),
     Decode(FromUtf8Error),
+    /// Error reported by the server inside a streamed (NDJSON) response
+    Stream(String),
+    /// HiveCore refused the token (401 or 403); `token` names which one
+    Unauthorized { status: u16, token: &'static str, body: String },
+    /// The endpoint or resource does not exist (404)
+    NotFound { url: String, body: String },
+    /// HiveCore failed while handling the request (5xx)
+    Server { status: u16, body: String },
+    /// Any other non-success status
+    Rejected { status: u16, body: String },
+    /// No response within the client timeout
+    Timeout(reqwest::Error),
+    /// Recent requests to the endpoint kept failing; not retried until the
+    /// circuit breaker lets one through again
+    CircuitOpen { endpoint: String, since: DateTime<Utc> },
+    /// A session recording that can't be replayed
+    Recording(String),
+}
+
+impl ClientError {
+    /// Classify a non-success response by its status code
+    pub fn from_status(status: u16, url: &str, token: &'static str, body: String) -> Self {
+        match status {
+            401 | 403 => ClientError::Unauthorized { status, token, body },
+            404 => ClientError::NotFound { url: url.to_string(), body },
+            500..=599 => ClientError::Server { status, body },
+            _ => ClientError::Rejected { status, body },
+        }
+    }
+
+    /// Failures worth retrying: HiveCore unreachable, slow or failing
+    /// internally, as opposed to rejecting the request
+    pub fn is_transient(&self) -> bool {
+        match self {
+            ClientError::Reqwest(e) => e.is_connect() || e.is_request(),
+            ClientError::Timeout(_) | ClientError::Server { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// HTTP status of a rejected request
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            ClientError::Unauthorized { status, .. }
+            | ClientError::Server { status, .. }
+            | ClientError::Rejected { status, .. } => Some(*status),
+            ClientError::NotFound { .. } => Some(404),
+            _ => None,
+        }
+    }
+}
+
+/// Longest part of a response body repeated in error messages
+const MAX_BODY_IN_MESSAGE: usize = 200;
+
+/// ": <body>" for error messages, on one line and shortened; empty for an
+/// empty body
+fn body_suffix(body: &str) -> String {
+    let body = body.split_whitespace().collect::<Vec<_>>().join(" ");
+    if body.is_empty() {
+        return String::new();
+    }
+    match body.char_indices().nth(MAX_BODY_IN_MESSAGE) {
+        Some((cut, _)) => format!(": {}…", &body[..cut]),
+        None => format!(": {}", body),
+    }
 }
 
 impl fmt::Display for ClientError {
@@ -26,6 +93,28 @@ impl fmt::Display for ClientError {
             ClientError::Toml(e) => write!(f, "TOML error: {}", e),
             ClientError::Io(e) => write!(f, "I/O error: {}", e),
             ClientError::Decode(e) => write!(f, "Decoding error: {}", e),
+            ClientError::Stream(message) => write!(f, "Server error: {}", message),
+            ClientError::Unauthorized { status, token, body } => {
+                write!(f, "{} rejected by HiveCore (HTTP {}){}", token, status, body_suffix(body))
+            }
+            ClientError::NotFound { url, body } => write!(f, "Not found: {}{}", url, body_suffix(body)),
+            ClientError::Server { status, body } => {
+                write!(f, "HiveCore internal error (HTTP {}){}", status, body_suffix(body))
+            }
+            ClientError::Rejected { status, body } => {
+                write!(f, "HiveCore rejected the request (HTTP {}){}", status, body_suffix(body))
+            }
+            ClientError::Timeout(e) => match e.url() {
+                Some(url) => write!(f, "HiveCore did not answer in time: {}", url),
+                None => write!(f, "HiveCore did not answer in time"),
+            },
+            ClientError::CircuitOpen { endpoint, since } => write!(
+                f,
+                "HiveCore unreachable since {} (/{}), retrying shortly",
+                since.with_timezone(&Local).format("%H:%M:%S"),
+                endpoint,
+            ),
+            ClientError::Recording(message) => write!(f, "Can't replay recording: {}", message),
                     }
     }
 }
@@ -39,13 +128,25 @@ impl StdError for ClientError {
             ClientError::Toml(e) => Some(e),
             ClientError::Io(e) => Some(e),
             ClientError::Decode(e) => Some(e),
+            ClientError::Timeout(e) => Some(e),
+            ClientError::Stream(_)
+            | ClientError::Unauthorized { .. }
+            | ClientError::NotFound { .. }
+            | ClientError::Server { .. }
+            | ClientError::Rejected { .. }
+            | ClientError::CircuitOpen { .. }
+            | ClientError::Recording(_) => None,
         }
     }
 }
 
 impl From<reqwest::Error> for ClientError {
     fn from(e: reqwest::Error) -> Self {
-        ClientError::Reqwest(e)
+        if e.is_timeout() {
+            ClientError::Timeout(e)
+        } else {
+            ClientError::Reqwest(e)
+        }
     }
 }
 
@@ -0,0 +1,131 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use futures::StreamExt;
+use tokio::sync::{mpsc, Mutex};
+
+use crate::app::{ActionPanelState, ActionType, App, BatchRow, BatchState, Focus};
+use crate::clients::infer_client::HiveInferClient;
+use crate::models::{ActionEvent, PullProgress, ACTION_EVENT_BUFFER};
+use crate::notify::EventKind;
+use crate::utils::format;
+
+/// Run a pull or delete of `model` on several workers.
+///
+/// At most `app.action_parallelism` workers run at once; the others wait
+/// as `Queued`. Each worker's row in `app.batch` follows the last status
+/// line of its stream and ends as `Done` or `Failed`.
+pub fn start(app: &mut App, app_arc: &Arc<Mutex<App>>, model: String, action_type: ActionType, workers: Vec<String>) {
+    let profile = app.profiles[app.active_profile].clone();
+    let client = match HiveInferClient::new(
+        format!("{}:{}", profile.host, profile.port_infer),
+        &profile.client_token,
+    ) {
+        Ok(c) => Arc::new(c),
+        Err(e) => {
+            app.add_banner(format!("Failed to create InferClient: {}", e));
+            app.action_panel_state = ActionPanelState::None;
+            app.focus = Focus::ActionsList;
+            return;
+        }
+    };
+
+    app.batch = workers.iter()
+        .map(|w| BatchRow { worker: w.clone(), state: BatchState::Queued })
+        .collect();
+    app.action_panel_state = ActionPanelState::Batch(model.clone(), action_type);
+    app.focus = Focus::ActionPanelResponse;
+    app.action_panel_scroll = 0;
+    app.is_action_in_progress = true;
+    let limit = app.action_parallelism.max(1);
+
+    let app_arc = app_arc.clone();
+    let handle = tokio::spawn(async move {
+        let results: Vec<(String, Result<String, String>)> = futures::stream::iter(workers)
+            .map(|worker| {
+                let client = client.clone();
+                let app_arc = app_arc.clone();
+                let model = model.clone();
+                async move {
+                    app_arc.lock().await.set_batch_state(&worker, BatchState::Running("starting".into()));
+                    let result = run_on_worker(&client, &app_arc, &model, action_type, &worker).await;
+                    let state = match &result {
+                        Ok(status) => BatchState::Done(status.clone()),
+                        Err(e) => BatchState::Failed(e.clone()),
+                    };
+                    app_arc.lock().await.set_batch_state(&worker, state);
+                    (worker, result)
+                }
+            })
+            .buffer_unordered(limit)
+            .collect()
+            .await;
+
+        let verb = if action_type == ActionType::Pull { "Pull" } else { "Delete" };
+        let mut app = app_arc.lock().await;
+        let failed: Vec<&(String, Result<String, String>)> = results.iter().filter(|(_, r)| r.is_err()).collect();
+        for (worker, result) in &failed {
+            if let Err(e) = result {
+                let message = format!("{} of {} on {} failed: {}", verb, model, worker, e);
+                app.notify(EventKind::ActionFailed, &format!("{}@{}", model, worker), &message);
+            }
+        }
+        app.add_banner(format!(
+            "{} of {}: {} succeeded, {} failed",
+            verb, model, results.len() - failed.len(), failed.len(),
+        ));
+        app.is_action_in_progress = false;
+    });
+    app.action_task_handle = Some(handle.abort_handle());
+}
+
+/// Run the action on one worker, returning its last status line or its
+/// first error
+async fn run_on_worker(
+    client: &HiveInferClient,
+    app_arc: &Arc<Mutex<App>>,
+    model: &str,
+    action_type: ActionType,
+    worker: &str,
+) -> Result<String, String> {
+    let (tx, mut rx) = mpsc::channel(ACTION_EVENT_BUFFER);
+    let action = async {
+        match action_type {
+            ActionType::Pull => client.pull_model(model, Some(worker), tx).await,
+            ActionType::Delete => client.delete_model(model, Some(worker), tx).await,
+        }
+    };
+    let watch = async {
+        let mut last_status = "done".to_string();
+        let mut first_error = None;
+        let mut progress = PullProgress::default();
+        while let Some(event) = rx.recv().await {
+            let detail = match event {
+                ActionEvent::Status(line) => {
+                    last_status = line;
+                    last_status.clone()
+                }
+                ActionEvent::Progress(line) => {
+                    progress.update(&line, Instant::now());
+                    let (completed, total) = progress.totals();
+                    format!(
+                        "{:.0}% of {} at {}/s",
+                        completed as f64 * 100.0 / total.max(1) as f64,
+                        format::bytes(total),
+                        format::bytes(progress.rate() as u64),
+                    )
+                }
+                ActionEvent::LineError(message) => {
+                    first_error.get_or_insert(message);
+                    continue;
+                }
+                ActionEvent::Finished { .. } => continue,
+            };
+            app_arc.lock().await.set_batch_state(worker, BatchState::Running(detail));
+        }
+        first_error.map_or(Ok(last_status), Err)
+    };
+    let (result, outcome) = tokio::join!(action, watch);
+    result.map_err(|e| e.to_string())?;
+    outcome
+}
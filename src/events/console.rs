@@ -0,0 +1,197 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use futures::StreamExt;
+use serde_json::Value;
+use tokio::sync::{Mutex, MutexGuard};
+
+use crate::app::{App, GenerationStats, PickerStage};
+use crate::clients::infer_client::HiveInferClient;
+use crate::errors::ClientError;
+use crate::models::{ChatMessage, ChatRequest};
+use crate::utils::ndjson;
+
+/// Where streamed text of a Console request ends up
+#[derive(Debug, Clone, Copy)]
+enum StreamTarget {
+    /// Generate mode: `App::console_output`
+    Output,
+    /// Chat mode: last message of the conversation at this index
+    Conversation(usize),
+}
+
+/// Stream a completion for the typed prompt into the Console output
+pub fn start_generate(app: &mut MutexGuard<'_, App>, app_arc: &Arc<Mutex<App>>) {
+    if !can_start(app) {
+        return;
+    }
+    let prompt = app.console_input.trim().to_string();
+    if prompt.is_empty() {
+        return;
+    }
+    let Some(model) = app.console_model() else {
+        app.add_banner("No model available for inference.");
+        return;
+    };
+
+    app.console_output.clear();
+    app.console_scroll = 0;
+    let node = app.console_node.clone();
+    let profile = app.profiles[app.active_profile].clone();
+    spawn_stream(app, app_arc, StreamTarget::Output, async move {
+        let client = HiveInferClient::new(
+            format!("{}:{}", profile.host, profile.port_infer),
+            &profile.client_token,
+        )?;
+        client.generate_stream(&model, &prompt, node.as_deref()).await
+    });
+}
+
+/// Append the typed message to the active conversation and stream the reply
+pub fn start_chat(app: &mut MutexGuard<'_, App>, app_arc: &Arc<Mutex<App>>) {
+    if !can_start(app) {
+        return;
+    }
+    let content = app.console_input.trim().to_string();
+    if content.is_empty() {
+        return;
+    }
+    let Some(model) = app.console_model() else {
+        app.add_banner("No model available for chat.");
+        return;
+    };
+
+    app.console_input.clear();
+    app.console_scroll = 0;
+    app.conversation_mut().messages.push(ChatMessage { role: "user".into(), content });
+    let request = ChatRequest {
+        model,
+        messages: app.conversation().request_messages(),
+        stream: true,
+    };
+    // The reply is streamed into this placeholder
+    app.conversation_mut().messages.push(ChatMessage { role: "assistant".into(), content: String::new() });
+    let target = StreamTarget::Conversation(app.active_conversation);
+    let node = app.console_node.clone();
+    let profile = app.profiles[app.active_profile].clone();
+    spawn_stream(app, app_arc, target, async move {
+        let client = HiveInferClient::new(
+            format!("{}:{}", profile.host, profile.port_infer),
+            &profile.client_token,
+        )?;
+        client.chat_stream(&request, node.as_deref()).await
+    });
+}
+
+/// Open the model/node picker and merge in the models the inference API
+/// reports, which may include ones no worker has tagged yet
+pub fn open_picker(app: &mut MutexGuard<'_, App>, app_arc: &Arc<Mutex<App>>) {
+    app.open_model_picker();
+    let profile = app.profiles[app.active_profile].clone();
+    let app_arc = app_arc.clone();
+    tokio::spawn(async move {
+        let models = match HiveInferClient::new(
+            format!("{}:{}", profile.host, profile.port_infer),
+            &profile.client_token,
+        ) {
+            Ok(client) => client.list_models(None).await,
+            Err(e) => Err(e),
+        };
+        if let Ok(models) = models {
+            let mut app = app_arc.lock().await;
+            if let Some(picker) = app.model_picker.as_mut().filter(|p| p.stage == PickerStage::Model) {
+                picker.merge_models(models);
+            }
+        }
+    });
+}
+
+fn can_start(app: &mut MutexGuard<'_, App>) -> bool {
+    if app.is_generating {
+        app.add_banner("Generation in progress. Press ESC to cancel it.");
+        return false;
+    }
+    true
+}
+
+/// Run `request` on a background task and feed its NDJSON body into `target`.
+/// The abort handle is kept on `App` so ESC can cancel the stream.
+fn spawn_stream<F>(app: &mut MutexGuard<'_, App>, app_arc: &Arc<Mutex<App>>, target: StreamTarget, request: F)
+where
+    F: std::future::Future<Output = Result<reqwest::Response, ClientError>> + Send + 'static,
+{
+    app.is_generating = true;
+    app.generation_stats = Some(GenerationStats::new());
+
+    let app_arc = app_arc.clone();
+    let handle = tokio::spawn(async move {
+        let result = match request.await {
+            Ok(resp) => read_stream(resp, target, &app_arc).await,
+            Err(e) => Err(e),
+        };
+        let mut app = app_arc.lock().await;
+        if let Err(e) = result {
+            app.add_banner(format!("Inference failed: {}", e));
+            // Drop the reply placeholder if nothing arrived
+            if let StreamTarget::Conversation(index) = target {
+                if let Some(conversation) = app.conversations.get_mut(index) {
+                    conversation.drop_empty_reply();
+                }
+            }
+        }
+        app.is_generating = false;
+        app.console_task_handle = None;
+        if let Some(stats) = &mut app.generation_stats {
+            stats.finished = Some(Instant::now());
+        }
+    });
+    app.console_task_handle = Some(handle.abort_handle());
+}
+
+async fn read_stream(resp: reqwest::Response, target: StreamTarget, app_arc: &Arc<Mutex<App>>) -> Result<(), ClientError> {
+    let mut events = Box::pin(ndjson::events::<Value>(resp));
+    while let Some(event) = events.next().await {
+        match event {
+            Ok(chunk) => apply_chunk(&chunk, target, app_arc).await,
+            Err(ClientError::Stream(message)) => {
+                app_arc.lock().await.add_banner(format!("Inference error: {}", message));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+async fn apply_chunk(chunk: &Value, target: StreamTarget, app_arc: &Arc<Mutex<App>>) {
+    let mut app = app_arc.lock().await;
+
+    let text = chunk.get("response")
+        .or_else(|| chunk.get("message").and_then(|m| m.get("content")))
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    if !text.is_empty() {
+        match target {
+            StreamTarget::Output => app.append_console_output(text),
+            StreamTarget::Conversation(index) => {
+                if let Some(message) = app.conversations.get_mut(index).and_then(|c| c.messages.last_mut()) {
+                    message.content.push_str(text);
+                }
+            }
+        }
+        if let Some(stats) = &mut app.generation_stats {
+            stats.first_token.get_or_insert_with(Instant::now);
+            stats.tokens += 1;
+        }
+    }
+
+    // Ollama reports exact counts in the final chunk
+    if chunk.get("done").and_then(Value::as_bool).unwrap_or(false) {
+        let eval_count = chunk.get("eval_count").and_then(Value::as_f64);
+        let eval_ns = chunk.get("eval_duration").and_then(Value::as_f64);
+        if let (Some(stats), Some(count), Some(ns)) = (&mut app.generation_stats, eval_count, eval_ns) {
+            if ns > 0.0 {
+                stats.reported_tps = Some(count / (ns / 1e9));
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use futures::stream::{FuturesUnordered, StreamExt};
+use tokio::sync::{Mutex, Notify};
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+
+use crate::app::{App, ClusterState, Resource, Tab};
+use crate::clients::manage_client::HiveManageClient;
+use crate::config::Profile;
+use crate::errors::ClientError;
+use crate::models::ClusterSummary;
+
+/// Background polling of every profile for the Fleet tab.
+///
+/// A single task fetches statuses, connections and the queue of all
+/// profiles concurrently, once per general interval, while the Fleet tab
+/// is open. Each profile's row is updated as soon as its answer arrives,
+/// so an unreachable cluster does not hold back the others. Clients are kept between cycles and rebuilt only when a
+/// profile's settings change.
+pub struct FleetPoller {
+    handle: Option<JoinHandle<()>>,
+    wake: Arc<Notify>,
+}
+
+impl FleetPoller {
+    pub fn spawn(app_arc: Arc<Mutex<App>>) -> Self {
+        let wake = Arc::new(Notify::new());
+        let task_wake = wake.clone();
+        let handle = tokio::spawn(async move {
+            fleet_loop(app_arc, task_wake).await;
+        });
+        FleetPoller { handle: Some(handle), wake }
+    }
+
+    /// Interrupt the sleeping task so it re-reads tab and intervals now
+    pub fn wake(&self) {
+        self.wake.notify_waiters();
+    }
+
+    pub fn stop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            handle.abort();
+        }
+    }
+}
+
+impl Drop for FleetPoller {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+async fn fleet_loop(app_arc: Arc<Mutex<App>>, wake: Arc<Notify>) {
+    let mut clients: Vec<(Profile, Result<Arc<HiveManageClient>, String>)> = Vec::new();
+
+    loop {
+        let (interval, profiles) = {
+            let app = app_arc.lock().await;
+            let profiles = (app.current_tab == Tab::Fleet).then(|| app.profiles.clone());
+            (app.intervals.for_resource(Resource::Statuses), profiles)
+        };
+
+        if let Some(profiles) = profiles {
+            clients = profiles.into_iter().map(|profile| {
+                let cached = clients.iter().position(|(p, _)| *p == profile);
+                match cached {
+                    Some(i) => clients.swap_remove(i),
+                    None => {
+                        let client = HiveManageClient::new(
+                            format!("{}:{}", profile.host, profile.port_manage),
+                            &profile.admin_token,
+                        ).map(Arc::new).map_err(|e| e.to_string());
+                        (profile, client)
+                    }
+                }
+            }).collect();
+
+            let mut results: FuturesUnordered<_> = clients.iter().map(|(profile, client)| {
+                let name = profile.name.clone();
+                let client = client.clone();
+                async move {
+                    let result = match client {
+                        Ok(client) => fetch_summary(&client).await.map_err(|e| e.to_string()),
+                        Err(e) => Err(e),
+                    };
+                    (name, result)
+                }
+            }).collect();
+
+            while let Some((name, result)) = results.next().await {
+                let mut app = app_arc.lock().await;
+                // Forget profiles that were deleted or renamed meanwhile
+                let names: Vec<String> = app.profiles.iter().map(|p| p.name.clone()).collect();
+                app.fleet.retain(|name, _| names.contains(name));
+                if !names.contains(&name) {
+                    continue;
+                }
+                let previous = app.fleet.remove(&name).and_then(|s| s.summary);
+                let state = match result {
+                    Ok(summary) => ClusterState { summary: Some(summary), error: None, updated: Utc::now() },
+                    Err(e) => ClusterState { summary: previous, error: Some(e), updated: Utc::now() },
+                };
+                app.fleet.insert(name, state);
+            }
+        }
+
+        tokio::select! {
+            _ = sleep(interval) => {},
+            _ = wake.notified() => {},
+        }
+    }
+}
+
+async fn fetch_summary(client: &HiveManageClient) -> Result<ClusterSummary, ClientError> {
+    let (statuses, connections, queue) = tokio::try_join!(
+        client.get_worker_status(),
+        client.get_worker_connections(),
+        client.get_queue(),
+    )?;
+    Ok(ClusterSummary::from_data(&statuses, &connections, &queue))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::mock::{self, MockHive, Reply};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn a_slow_cluster_does_not_hold_back_the_others() {
+        let fast = MockHive::start().await.unwrap();
+        let slow = MockHive::start().await.unwrap();
+        slow.script("GET", "/worker/status", [Reply::json(serde_json::json!({})).delayed(Duration::from_secs(3))]);
+        let mut app = App::new(vec![slow.profile("slow"), fast.profile("fast")]);
+        app.current_tab = Tab::Fleet;
+        let app_arc = Arc::new(Mutex::new(app));
+        let _poller = FleetPoller::spawn(app_arc.clone());
+
+        mock::wait_until(&app_arc, "the fast cluster's row", |app| app.fleet.contains_key("fast")).await;
+        let app = app_arc.lock().await;
+        assert!(app.fleet["fast"].summary.is_some());
+        assert!(!app.fleet.contains_key("slow"));
+    }
+}
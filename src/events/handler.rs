@@ -1,42 +1,55 @@
 use std::sync::Arc;
 
-use tokio::sync::Mutex;
+use tokio::sync::{mpsc, Mutex};
 
-use futures::{StreamExt, TryStreamExt};
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyModifiers};
 
-use crate::{app::{ActionPanelState, ActionType, App, Focus, Tab}, clients::{infer_client::HiveInferClient, manage_client::HiveManageClient}, events::spawner::{Event, EventSpawner}, models::{GenerateRequest, GenerateResponse}};
+use crate::{app::{ActionPanelState, ActionTarget, ActionType, App, ConsoleMode, Focus, Tab}, clients::{infer_client::HiveInferClient, manage_client::HiveManageClient}, models::{ActionEvent, ACTION_EVENT_BUFFER}, notify::EventKind, events::{batch, console, fleet::FleetPoller, poller::Poller, profiles, replay::{self, ReplayPlayer}, spawner::{Event, EventSpawner}}};
 
 pub async fn handle_events(mut event_spawner: EventSpawner, app_arc: Arc<Mutex<App>>) {
-    let manage_client = {
-        let app = app_arc.lock().await;
-        let profile = &app.profiles[app.active_profile];
-        match HiveManageClient::new(
-            format!("{}:{}", profile.host, profile.port_manage),
-            &profile.admin_token,
-        ) {
-            Ok(c) => c,
-            Err(e) => {
-                app_arc.clone().lock().await.add_banner(format!("Can't contact HiveCore Manage API: {}", e));
-                return;
-            },
-        }
-    };
+    let mut poller: Option<Poller> = None;
+    let mut poller_epoch = None;
+    let mut last_poll_view = None;
+    // A replay stands in for every poller, and there is no fleet to poll
+    let replaying = app_arc.lock().await.replay.is_some();
+    let mut fleet_poller = (!replaying).then(|| FleetPoller::spawn(app_arc.clone()));
+    let mut replay_player = replaying.then(|| ReplayPlayer::spawn(app_arc.clone()));
 
     loop {
-        // Capture necessary state values from app_arc at the start of each loop iteration
-        let current_tab;
-        let current_focus;
-        // current_action_panel_state is now less directly used outside locks
-        // let current_action_panel_state;
         { // Scope to release lock quickly
-            let app = app_arc.lock().await;
-            current_tab = app.current_tab;
-            current_focus = app.focus;
-            // current_action_panel_state = app.action_panel_state.clone();
+            let mut app = app_arc.lock().await;
+
+            // (Re)connect when the active profile or its settings changed
+            if !replaying && poller_epoch != Some(app.profile_epoch) {
+                poller_epoch = Some(app.profile_epoch);
+                if let Some(mut old) = poller.take() {
+                    old.stop();
+                }
+                let profile = &app.profiles[app.active_profile];
+                match HiveManageClient::new(
+                    format!("{}:{}", profile.host, profile.port_manage),
+                    &profile.admin_token,
+                ) {
+                    Ok(c) => poller = Some(Poller::spawn(Arc::new(c), app_arc.clone(), app.profile_epoch)),
+                    Err(e) => app.add_banner(format!("Can't contact HiveCore Manage API: {}", e)),
+                }
+            }
+
+            // Wake the poller early when the previous input changed what it should fetch
+            let poll_view = (app.current_tab, app.intervals.queue_secs, app.intervals.general_secs);
+            if last_poll_view.is_some_and(|last| last != poll_view) {
+                if let Some(poller) = &poller {
+                    poller.wake();
+                }
+                if let Some(fleet_poller) = &fleet_poller {
+                    fleet_poller.wake();
+                }
+            }
+            last_poll_view = Some(poll_view);
         } // `app` MutexGuard is dropped here.
 
-        match event_spawner.next().await {
+        let event = event_spawner.next().await;
+        match event {
             Event::Input(key) => {
                 // If an action is in progress and we are NOT in the response view,
                 // we should prevent most input. The `focus_left/right` already
@@ -70,17 +83,124 @@ pub async fn handle_events(mut event_spawner: EventSpawner, app_arc: Arc<Mutex<A
 
                 let mut app = app_arc.lock().await; // Lock once for input handling
 
+                // The profiles overlay captures all keys while open
+                if app.profiles_view.is_some() {
+                    profiles::on_profiles_key(&mut app, key);
+                    continue;
+                }
+                if key.code == KeyCode::F(4) {
+                    app.open_profiles_view();
+                    continue;
+                }
+                // Playback keys, except where they are typed into the Console
+                let browsing = matches!(app.focus, Focus::WorkersList | Focus::ActionsList | Focus::GlobalView);
+                if browsing && app.current_tab != Tab::Console && replay::on_replay_key(&mut app, key) {
+                    continue;
+                }
+
                 match app.focus {
+                    Focus::WorkersList | Focus::ActionsList | Focus::GlobalView if app.current_tab == Tab::Console && app.model_picker.is_some() => {
+                        match key.code {
+                            KeyCode::Up | KeyCode::Char('k') => app.move_picker(false),
+                            KeyCode::Down | KeyCode::Char('j') => app.move_picker(true),
+                            KeyCode::Enter => app.confirm_picker(),
+                            KeyCode::Esc | KeyCode::F(3) => app.model_picker = None,
+                            _ => {}
+                        }
+                    },
+                    Focus::WorkersList | Focus::ActionsList | Focus::GlobalView if app.current_tab == Tab::Console => {
+                        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+                        match key.code {
+                            KeyCode::Tab => app.next_tab(),
+                            KeyCode::BackTab => app.prev_tab(),
+                            KeyCode::F(2) => app.toggle_console_mode(),
+                            KeyCode::F(3) => console::open_picker(&mut app, &app_arc),
+                            KeyCode::Char('l') if ctrl => app.clear_conversation(),
+                            KeyCode::Char('f') if ctrl => app.fork_conversation(),
+                            KeyCode::Char('n') if ctrl => app.cycle_conversation(true),
+                            KeyCode::Char('p') if ctrl => app.cycle_conversation(false),
+                            KeyCode::Char('s') if ctrl => on_edit_system_prompt(&mut app),
+                            KeyCode::PageUp => app.console_scroll = app.console_scroll.saturating_add(5),
+                            KeyCode::PageDown => app.console_scroll = app.console_scroll.saturating_sub(5),
+                            KeyCode::Esc => {
+                                if app.is_generating {
+                                    app.cancel_generation();
+                                    app.add_banner("Generation cancelled.");
+                                } else {
+                                    app.editing_system_prompt = false;
+                                    app.console_input.clear();
+                                }
+                            },
+                            KeyCode::Enter => {
+                                if app.editing_system_prompt {
+                                    on_save_system_prompt(&mut app);
+                                } else if app.console_mode == ConsoleMode::Chat {
+                                    console::start_chat(&mut app, &app_arc);
+                                } else {
+                                    console::start_generate(&mut app, &app_arc);
+                                }
+                            },
+                            KeyCode::Backspace => on_backspace(app),
+                            KeyCode::Char(c) if !ctrl => on_unhandled_character(&mut app, c),
+                            _ => {}
+                        }
+                    },
+                    Focus::WorkersList if app.current_tab == Tab::Dashboard && app.worker_detail.is_some() => {
+                        match key.code {
+                            KeyCode::Char('q') => break,
+                            KeyCode::Esc | KeyCode::Enter | KeyCode::Backspace => app.worker_detail = None,
+                            KeyCode::Up | KeyCode::Char('w') => app.worker_detail_scroll = app.worker_detail_scroll.saturating_sub(1),
+                            KeyCode::Down | KeyCode::Char('s') => app.worker_detail_scroll = app.worker_detail_scroll.saturating_add(1),
+                            KeyCode::Tab => app.next_tab(),
+                            KeyCode::BackTab => app.prev_tab(),
+                            _ => {}
+                        }
+                    },
+                    Focus::WorkersList | Focus::ActionsList | Focus::GlobalView if app.current_tab == Tab::Models && matches!(key.code, KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right | KeyCode::Char('w') | KeyCode::Char('a') | KeyCode::Char('s') | KeyCode::Char('d') | KeyCode::Enter) => {
+                        match key.code {
+                            KeyCode::Up | KeyCode::Char('w') => app.move_models_cursor(-1, 0),
+                            KeyCode::Down | KeyCode::Char('s') => app.move_models_cursor(1, 0),
+                            KeyCode::Left | KeyCode::Char('a') => app.move_models_cursor(0, -1),
+                            KeyCode::Right | KeyCode::Char('d') => app.move_models_cursor(0, 1),
+                            _ => app.act_on_models_cell(),
+                        }
+                    },
+                    Focus::WorkersList | Focus::ActionsList | Focus::GlobalView if app.current_tab == Tab::Fleet && matches!(key.code, KeyCode::Up | KeyCode::Down | KeyCode::Char('w') | KeyCode::Char('s') | KeyCode::Enter) => {
+                        let last = app.profiles.len().saturating_sub(1);
+                        match key.code {
+                            KeyCode::Up | KeyCode::Char('w') => app.fleet_selected = app.fleet_selected.saturating_sub(1),
+                            KeyCode::Down | KeyCode::Char('s') => app.fleet_selected = (app.fleet_selected + 1).min(last),
+                            _ => app.drill_into_cluster(),
+                        }
+                    },
+                    Focus::WorkersList | Focus::ActionsList | Focus::GlobalView if app.current_tab == Tab::Alerts && matches!(key.code, KeyCode::Up | KeyCode::Down | KeyCode::Char('w') | KeyCode::Char('s') | KeyCode::Char('a') | KeyCode::Char('m')) => {
+                        let last = app.alerts.listed().len().saturating_sub(1);
+                        match key.code {
+                            KeyCode::Up | KeyCode::Char('w') => app.alerts_selected = app.alerts_selected.saturating_sub(1),
+                            KeyCode::Down | KeyCode::Char('s') => app.alerts_selected = (app.alerts_selected + 1).min(last),
+                            KeyCode::Char('a') => app.acknowledge_selected_alert(),
+                            _ => app.toggle_silence_selected_alert(),
+                        }
+                    },
                     Focus::WorkersList | Focus::ActionsList | Focus::GlobalView => {
                         match key.code {
                             KeyCode::Char('q') => break,
+                            KeyCode::Tab => app.next_tab(),
+                            KeyCode::BackTab => app.prev_tab(),
+                            KeyCode::Char(c @ '1'..='9') => app.select_tab(c as usize - '1' as usize),
+                            KeyCode::Char('+') | KeyCode::Char('=') => on_change_interval(&mut app, false, false),
+                            KeyCode::Char('-') => on_change_interval(&mut app, false, true),
+                            KeyCode::Char(']') => on_change_interval(&mut app, true, false),
+                            KeyCode::Char('[') => on_change_interval(&mut app, true, true),
                             KeyCode::Left | KeyCode::Char('a') => on_key_left(&mut app),
                             KeyCode::Right | KeyCode::Char('d') => on_key_right(&mut app),
                             KeyCode::Up | KeyCode::Char('w') => on_key_up(&mut app),
                             KeyCode::Down | KeyCode::Char('s') => on_key_down(&mut app),
                             KeyCode::Char('r') => on_key_r(&mut app),
-                            KeyCode::Enter => on_enter_main_view(&mut app).await,
+                            KeyCode::Char('p') => app.open_profiles_view(),
+                            KeyCode::Enter => on_enter_main_view(&mut app),
                             KeyCode::Backspace => on_backspace(app),
+                            KeyCode::Char(' ') if app.current_tab == Tab::Dashboard && app.focus == Focus::WorkersList => app.toggle_marked_worker(),
                             KeyCode::Char(c) => on_unhandled_character(&mut app, c),
                             _ => {}
                         }
@@ -118,6 +238,9 @@ pub async fn handle_events(mut event_spawner: EventSpawner, app_arc: Arc<Mutex<A
                             KeyCode::Backspace => {
                                 app.backspace_action_field();
                             },
+                            KeyCode::Tab => {
+                                app.cycle_action_target();
+                            },
                             KeyCode::Left => {
                                 app.focus_left();
                             },
@@ -138,6 +261,23 @@ pub async fn handle_events(mut event_spawner: EventSpawner, app_arc: Arc<Mutex<A
                                 app.confirmation_selection = 1 - app.confirmation_selection;
                             },
 
+                            KeyCode::Enter if app.action_target != ActionTarget::Selected => {
+                                if let ActionPanelState::Confirmation(model_name, action_type) = app.action_panel_state.clone() {
+                                    let workers = app.target_workers(&model_name, action_type);
+                                    if app.confirmation_selection != 0 {
+                                        app.action_panel_state = ActionPanelState::None;
+                                        app.focus = Focus::ActionsList;
+                                        app.action_input_model_name.clear();
+                                        app.action_input_cursor_position = 0;
+                                        app.add_banner("Action cancelled by user.");
+                                    } else if workers.is_empty() {
+                                        let target = app.action_target.label(action_type);
+                                        app.add_banner(format!("No workers match target: {}.", target));
+                                    } else {
+                                        batch::start(&mut app, &app_arc, model_name, action_type, workers);
+                                    }
+                                }
+                            },
                             KeyCode::Enter => {
                                 let action_state = app.action_panel_state.clone();
                                 let model_name_for_action = if let ActionPanelState::Confirmation(ref m_name, _) = action_state {
@@ -150,11 +290,9 @@ pub async fn handle_events(mut event_spawner: EventSpawner, app_arc: Arc<Mutex<A
                                 let client_token = profile.client_token.clone();
 
                                 // Set initial processing state for UI, then drop lock
-                                app.action_panel_state = ActionPanelState::Response(
+                                app.begin_action_response(
                                     model_name_for_action.clone(),
                                     match action_state { ActionPanelState::Confirmation(_, action_type) => action_type, _ => ActionType::Pull },
-                                    vec!["Initiating action...".to_string()],
-                                    true,
                                 );
                                 app.focus = Focus::ActionPanelResponse;
                                 app.is_action_in_progress = true; // Set flag
@@ -190,15 +328,23 @@ pub async fn handle_events(mut event_spawner: EventSpawner, app_arc: Arc<Mutex<A
                                             ActionPanelState::Confirmation(model_name, action_type) => {
                                                 match action_type {
                                                     ActionType::Pull => {
-                                                        match infer_client.pull_model(&model_name, node, app_arc_for_spawn.clone()).await {
-                                                            Ok(_) => {},
-                                                            Err(e) => api_overall_result_message = Err(format!("Pull failed: {}", e)),
+                                                        let (tx, rx) = mpsc::channel(ACTION_EVENT_BUFFER);
+                                                        let (result, ()) = tokio::join!(
+                                                            infer_client.pull_model(&model_name, node, tx),
+                                                            forward_action_events(rx, &app_arc_for_spawn),
+                                                        );
+                                                        if let Err(e) = result {
+                                                            api_overall_result_message = Err(format!("Pull failed: {}", e));
                                                         }
                                                     },
                                                     ActionType::Delete => {
-                                                        match infer_client.delete_model(&model_name, node, app_arc_for_spawn.clone()).await {
-                                                            Ok(_) => {},
-                                                            Err(e) => api_overall_result_message = Err(format!("Delete failed: {}", e)),
+                                                        let (tx, rx) = mpsc::channel(ACTION_EVENT_BUFFER);
+                                                        let (result, ()) = tokio::join!(
+                                                            infer_client.delete_model(&model_name, node, tx),
+                                                            forward_action_events(rx, &app_arc_for_spawn),
+                                                        );
+                                                        if let Err(e) = result {
+                                                            api_overall_result_message = Err(format!("Delete failed: {}", e));
                                                         }
                                                     },
                                                 }
@@ -212,11 +358,18 @@ pub async fn handle_events(mut event_spawner: EventSpawner, app_arc: Arc<Mutex<A
                                     // After the operation (streaming or single call) is done,
                                     // ensure the overall status is reflected and clean up.
                                     let mut app = app_arc_for_spawn.lock().await;
-                                    if let ActionPanelState::Response(ref _m_name, ref _act_type, ref mut lines, ref mut overall_success) = app.action_panel_state {
+                                    let mut failure = None;
+                                    if let ActionPanelState::Response(ref m_name, ref act_type, ref mut lines, ref mut overall_success) = app.action_panel_state {
                                         if api_overall_result_message.is_err() {
                                             *overall_success = false;
                                             lines.push(api_overall_result_message.unwrap_err()); // Push the error message
                                         }
+                                        if !*overall_success {
+                                            let verb = if *act_type == ActionType::Pull { "Pull" } else { "Delete" };
+                                            let worker = selected_worker_name.as_deref().unwrap_or("any worker");
+                                            let detail = lines.last().cloned().unwrap_or_default();
+                                            failure = Some((format!("{}@{}", m_name, worker), format!("{} of {} on {} failed: {}", verb, m_name, worker, detail)));
+                                        }
                                         // Auto-scroll to bottom of logs on completion/final update
                                         // This is a common UX for streaming logs
                                         app.action_panel_scroll = lines.len().saturating_sub(
@@ -227,6 +380,9 @@ pub async fn handle_events(mut event_spawner: EventSpawner, app_arc: Arc<Mutex<A
                                             1 // Minimum height for 1 line to be visible at end
                                         ) as u16;
                                     }
+                                    if let Some((subject, message)) = failure {
+                                        app.notify(EventKind::ActionFailed, &subject, &message);
+                                    }
                                     app.is_action_in_progress = false; // Action is now complete, reset flag
                                 }).abort_handle(); // Get the AbortHandle
 
@@ -277,39 +433,57 @@ pub async fn handle_events(mut event_spawner: EventSpawner, app_arc: Arc<Mutex<A
                     _ => {}
                 }
             },
-            Event::Tick => {
-                // Polling logic remains the same
-                match current_tab {
-                   Tab::Dashboard => {
-                        if let Ok(resp) = manage_client.get_worker_status().await {
-                            let mut app = app_arc.lock().await;
-                            app.worker_statuses = Some(resp);
-                        }
-                        if let Ok(resp) = manage_client.get_worker_connections().await {
-                            let mut app = app_arc.lock().await;
-                            app.worker_connections = Some(resp);
-                        }
-                        if let Ok(resp) = manage_client.get_worker_pings().await {
-                            let mut app = app_arc.lock().await;
-                            app.worker_pings = Some(resp);
-                        }
-                        if let Ok(resp) = manage_client.get_worker_versions().await {
-                            let mut app = app_arc.lock().await;
-                            app.worker_versions = Some(resp);
-                        }
-                        if let Ok(resp) = manage_client.get_worker_tags().await {
-                            let mut app = app_arc.lock().await;
-                            app.worker_tags = Some(resp);
-                        }
-                    }
-                   _ => {}
-                }
-            }
+            // Data is refreshed by the poller tasks; ticks only drive redraw-side state
+            Event::Tick => {}
             Event::Stop => break,
         }
     }
+    if let Some(mut poller) = poller {
+        poller.stop();
+    }
+    if let Some(mut fleet_poller) = fleet_poller.take() {
+        fleet_poller.stop();
+    }
+    if let Some(mut replay_player) = replay_player.take() {
+        replay_player.stop();
+    }
+}
+
+
+/// Apply the events of a running single-worker action until the client is done
+async fn forward_action_events(mut events: mpsc::Receiver<ActionEvent>, app_arc: &Arc<Mutex<App>>) {
+    while let Some(event) = events.recv().await {
+        app_arc.lock().await.apply_action_event(event);
+    }
 }
 
+fn on_edit_system_prompt(app: &mut tokio::sync::MutexGuard<'_, App>) {
+    if app.console_mode != ConsoleMode::Chat {
+        app.add_banner("System prompts apply to chat mode (F2 to switch).");
+        return;
+    }
+    app.editing_system_prompt = true;
+    app.console_input = app.conversation().system_prompt.clone();
+}
+
+fn on_save_system_prompt(app: &mut tokio::sync::MutexGuard<'_, App>) {
+    let prompt = std::mem::take(&mut app.console_input);
+    app.conversation_mut().system_prompt = prompt.trim().to_string();
+    app.editing_system_prompt = false;
+}
+
+fn on_change_interval(app: &mut tokio::sync::MutexGuard<'_, App>, queue: bool, faster: bool) {
+    if queue {
+        app.intervals.scale_queue(faster);
+    } else {
+        app.intervals.step_general(faster);
+    }
+    let msg = format!(
+        "Polling: queue every {:.1}s, other resources every {}s",
+        app.intervals.queue_secs, app.intervals.general_secs
+    );
+    app.add_banner(msg);
+}
 
 fn on_key_down(app: &mut tokio::sync::MutexGuard<'_, App>) {
     app.focus_down();
@@ -344,9 +518,11 @@ fn on_key_left(app: &mut tokio::sync::MutexGuard<'_, App>) {
 }
 
 // Renamed and modified `on_enter` to `on_enter_main_view`
-async fn on_enter_main_view(app: &mut tokio::sync::MutexGuard<'_, App>) { // No app_arc here needed
+fn on_enter_main_view(app: &mut tokio::sync::MutexGuard<'_, App>) { // No app_arc here needed
     if app.current_tab == Tab::Dashboard {
-        if app.focus == Focus::ActionsList {
+        if app.focus == Focus::WorkersList {
+            app.open_worker_detail();
+        } else if app.focus == Focus::ActionsList {
             let selected_action_name = app.worker_actions.get(app.selected_action).copied();
             // This is the source of the problem: `selected_model_name` is from the old flow
             // and should not be used here to directly jump to Confirmation.
@@ -357,6 +533,7 @@ async fn on_enter_main_view(app: &mut tokio::sync::MutexGuard<'_, App>) { // No
                 Some("Pull model") => {
                     // Corrected: Transition to input state
                     app.action_panel_state = ActionPanelState::PullModel;
+                    app.reset_action_target();
                     app.focus = Focus::ActionPanelInput;
                     app.action_input_model_name.clear(); // Clear input field
                     app.action_input_cursor_position = 0;
@@ -364,6 +541,7 @@ async fn on_enter_main_view(app: &mut tokio::sync::MutexGuard<'_, App>) { // No
                 Some("Delete model") => {
                     // Corrected: Transition to input state
                     app.action_panel_state = ActionPanelState::DeleteModel;
+                    app.reset_action_target();
                     app.focus = Focus::ActionPanelInput;
                     app.action_input_model_name.clear(); // Clear input field
                     app.action_input_cursor_position = 0;
@@ -371,37 +549,104 @@ async fn on_enter_main_view(app: &mut tokio::sync::MutexGuard<'_, App>) { // No
                 _ => {}
             }
         }
-    } else if app.current_tab == Tab::Console {
-        // Original console logic for generate
-        let profile = &app.profiles[app.active_profile];
-        let api = match HiveInferClient::new(
-            format!("{}:{}", profile.host, profile.port_infer),
-            &profile.client_token,
-        ) {
-            Ok(c) => c,
-            Err(e) => {
-                app.add_banner(format!("Can't contact HiveCore: {}", e));
-                return;
-            },
-        };
-        let model = app.queue_map
-            .as_ref()
-            .and_then(|qm| qm.keys().next().cloned())
-            .unwrap_or_default();
-        let req = GenerateRequest {
-            model: model.clone(),
-            prompt: app.console_input.clone(),
-            stream: false,
-            node: None,
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyEvent;
+
+    use super::*;
+    use crate::app::BatchState;
+    use crate::mock::{self, MockHive};
+
+    /// Run the handler against `hive` with scripted input; the app starts
+    /// once the workers list is loaded
+    async fn start(hive: &MockHive) -> (Arc<Mutex<App>>, mpsc::Sender<Event>, tokio::task::JoinHandle<()>) {
+        let mut app = App::new(vec![hive.profile("mock")]);
+        app.set_active_profile(0);
+        let app_arc = Arc::new(Mutex::new(app));
+        let (spawner, tx) = EventSpawner::scripted();
+        let handler = tokio::spawn(handle_events(spawner, app_arc.clone()));
+        mock::wait_until(&app_arc, "the workers list", |app| app.worker_statuses.is_some()).await;
+        (app_arc, tx, handler)
+    }
+
+    async fn press(tx: &mpsc::Sender<Event>, keys: &[KeyCode]) {
+        for key in keys {
+            tx.send(Event::Input(KeyEvent::new(*key, KeyModifiers::NONE))).await.unwrap();
+        }
+    }
+
+    async fn type_text(tx: &mpsc::Sender<Event>, text: &str) {
+        let keys: Vec<KeyCode> = text.chars().map(KeyCode::Char).collect();
+        press(tx, &keys).await;
+    }
+
+    #[tokio::test]
+    async fn pulls_a_model_on_the_selected_worker() {
+        let hive = MockHive::start().await.unwrap();
+        let (app_arc, tx, _handler) = start(&hive).await;
+
+        press(&tx, &[KeyCode::Right, KeyCode::Enter]).await;
+        type_text(&tx, "phi3").await;
+        press(&tx, &[KeyCode::Enter, KeyCode::Enter]).await;
+
+        mock::wait_until(&app_arc, "the pull to finish", |app| {
+            !app.is_action_in_progress && matches!(app.action_panel_state, ActionPanelState::Response(..))
+        }).await;
+        let app = app_arc.lock().await;
+        let ActionPanelState::Response(model, ActionType::Pull, lines, success) = &app.action_panel_state else {
+            panic!("unexpected panel state {:?}", app.action_panel_state);
         };
-        match api.generate(&req.model, &req.prompt, None, req.stream).await {
-            Ok(raw) => {
-                if let Ok(resp) = serde_json::from_value::<GenerateResponse>(raw) {
-                    app.generate_response = Some(resp.clone());
-                    app.console_output = vec![resp.result];
-                }
-            }
-            Err(e) => app.add_banner(format!("Inference failed: {}", e)),
+        assert_eq!(model, "phi3");
+        assert!(success, "pull failed: {:?}", lines);
+        assert!(lines.iter().any(|l| l == "success"), "{:?}", lines);
+        assert_eq!(hive.models_on("cpu-01"), vec!["nomic-embed-text:latest", "phi3"]);
+    }
+
+    #[tokio::test]
+    async fn pulls_on_marked_workers_in_a_batch() {
+        let hive = MockHive::start().await.unwrap();
+        let (app_arc, tx, _handler) = start(&hive).await;
+
+        // Mark cpu-01 and gpu-02, the first and the last worker
+        press(&tx, &[KeyCode::Char(' '), KeyCode::Down, KeyCode::Down, KeyCode::Char(' ')]).await;
+        press(&tx, &[KeyCode::Right, KeyCode::Enter]).await;
+        type_text(&tx, "phi3").await;
+        // Marks preselect the marked workers as target
+        press(&tx, &[KeyCode::Enter, KeyCode::Enter]).await;
+
+        mock::wait_until(&app_arc, "the batch to finish", |app| {
+            !app.is_action_in_progress && matches!(app.action_panel_state, ActionPanelState::Batch(..))
+        }).await;
+        let app = app_arc.lock().await;
+        let workers: Vec<&str> = app.batch.iter().map(|row| row.worker.as_str()).collect();
+        assert_eq!(workers, vec!["cpu-01", "gpu-02"]);
+        assert!(app.batch.iter().all(|row| matches!(row.state, BatchState::Done(_))), "{:?}", app.batch);
+        assert!(app.banners.iter().any(|b| b == "Pull of phi3: 2 succeeded, 0 failed"));
+        assert!(hive.models_on("gpu-02").contains(&"phi3".to_string()));
+        assert!(!hive.models_on("gpu-01").contains(&"phi3".to_string()));
+    }
+
+    #[tokio::test]
+    async fn reports_a_failed_delete_and_quits() {
+        let hive = MockHive::start().await.unwrap();
+        let (app_arc, tx, handler) = start(&hive).await;
+
+        press(&tx, &[KeyCode::Right, KeyCode::Down, KeyCode::Enter]).await;
+        type_text(&tx, "phi3").await;
+        press(&tx, &[KeyCode::Enter, KeyCode::Enter]).await;
+
+        mock::wait_until(&app_arc, "the delete to fail", |app| {
+            !app.is_action_in_progress && matches!(app.action_panel_state, ActionPanelState::Response(_, _, _, false))
+        }).await;
+        if let ActionPanelState::Response(_, action_type, lines, _) = &app_arc.lock().await.action_panel_state {
+            assert_eq!(*action_type, ActionType::Delete);
+            assert!(lines.last().unwrap().contains("model 'phi3' not found"), "{:?}", lines);
         }
+
+        press(&tx, &[KeyCode::Esc, KeyCode::Char('q')]).await;
+        tokio::time::timeout(std::time::Duration::from_secs(5), handler).await.unwrap().unwrap();
+        assert_eq!(app_arc.lock().await.action_panel_state, ActionPanelState::None);
     }
-}
\ No newline at end of file
+}
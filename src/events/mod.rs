@@ -1,2 +1,8 @@
 pub mod spawner;
-pub mod handler;
\ No newline at end of file
+pub mod handler;
+pub mod poller;
+pub mod console;
+pub mod profiles;
+pub mod fleet;
+pub mod batch;
+pub mod replay;
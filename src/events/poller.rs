@@ -0,0 +1,163 @@
+use std::sync::Arc;
+
+use tokio::sync::{Mutex, Notify};
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+
+use crate::app::{App, PollData, Resource};
+use crate::clients::manage_client::HiveManageClient;
+use crate::errors::ClientError;
+
+/// Background polling of the management API, one task per resource.
+///
+/// Each task reads its cadence from `App::intervals` before sleeping, so
+/// interval changes take effect on the next cycle. Resources that neither
+/// the active tab nor an alert rule uses are skipped until that changes.
+pub struct Poller {
+    handles: Vec<JoinHandle<()>>,
+    wake: Arc<Notify>,
+}
+
+impl Poller {
+    /// Spawn one polling task per resource. Results are dropped once
+    /// `App::profile_epoch` moves past `epoch`.
+    pub fn spawn(client: Arc<HiveManageClient>, app_arc: Arc<Mutex<App>>, epoch: u64) -> Self {
+        let wake = Arc::new(Notify::new());
+        let handles = Resource::all()
+            .iter()
+            .map(|resource| {
+                let client = client.clone();
+                let app_arc = app_arc.clone();
+                let wake = wake.clone();
+                let resource = *resource;
+                tokio::spawn(async move {
+                    poll_loop(resource, client, app_arc, wake, epoch).await;
+                })
+            })
+            .collect();
+        Poller { handles, wake }
+    }
+
+    /// Interrupt every sleeping task so it re-reads tab and intervals now
+    pub fn wake(&self) {
+        self.wake.notify_waiters();
+    }
+
+    /// Abort all polling tasks
+    pub fn stop(&mut self) {
+        for handle in self.handles.drain(..) {
+            handle.abort();
+        }
+    }
+}
+
+impl Drop for Poller {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+async fn poll_loop(
+    resource: Resource,
+    client: Arc<HiveManageClient>,
+    app_arc: Arc<Mutex<App>>,
+    wake: Arc<Notify>,
+    epoch: u64,
+) {
+    loop {
+        let (interval, wanted) = {
+            let app = app_arc.lock().await;
+            (app.intervals.for_resource(resource), app.is_polled(resource))
+        };
+
+        if wanted {
+            let result = fetch(&client, resource).await;
+            let mut app = app_arc.lock().await;
+            if app.profile_epoch != epoch {
+                return;
+            }
+            match result {
+                Ok(data) => app.apply_poll(data),
+                Err(e) => app.record_poll_error(resource, &e),
+            }
+        }
+
+        tokio::select! {
+            _ = sleep(interval) => {},
+            _ = wake.notified() => {},
+        }
+    }
+}
+
+/// Fetch a single management resource
+pub async fn fetch(client: &HiveManageClient, resource: Resource) -> Result<PollData, ClientError> {
+    Ok(match resource {
+        Resource::Queue => PollData::Queue(client.get_queue().await?),
+        Resource::Statuses => PollData::Statuses(client.get_worker_status().await?),
+        Resource::Connections => PollData::Connections(client.get_worker_connections().await?),
+        Resource::Pings => PollData::Pings(client.get_worker_pings().await?),
+        Resource::Versions => PollData::Versions(client.get_worker_versions().await?),
+        Resource::Tags => PollData::Tags(client.get_worker_tags().await?),
+        Resource::Keys => PollData::Keys(client.get_keys().await?),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::{ConnectionStatus, EndpointHealth, Tab};
+    use crate::mock::{self, MockHive, Reply, ADMIN_TOKEN};
+
+    use super::*;
+
+    async fn app_on(hive: &MockHive, tab: Tab) -> Arc<Mutex<App>> {
+        let mut app = App::new(vec![hive.profile("mock")]);
+        app.set_active_profile(0);
+        app.current_tab = tab;
+        Arc::new(Mutex::new(app))
+    }
+
+    #[tokio::test]
+    async fn fetches_every_resource() {
+        let hive = MockHive::start().await.unwrap();
+        let client = HiveManageClient::new(hive.url(), ADMIN_TOKEN).unwrap();
+
+        for resource in Resource::all() {
+            assert_eq!(fetch(&client, *resource).await.unwrap().resource(), *resource);
+        }
+    }
+
+    #[tokio::test]
+    async fn a_failing_endpoint_does_not_hide_the_others() {
+        let hive = MockHive::start().await.unwrap();
+        hive.script("GET", "/worker/versions", [Reply::status(403, "versions are admin only")]);
+        let app_arc = app_on(&hive, Tab::Nodes).await;
+        let client = Arc::new(HiveManageClient::new(hive.url(), ADMIN_TOKEN).unwrap());
+        let epoch = app_arc.lock().await.profile_epoch;
+        let _poller = Poller::spawn(client, app_arc.clone(), epoch);
+
+        mock::wait_until(&app_arc, "versions to fail", |app| {
+            matches!(app.endpoint_health.get(&Resource::Versions), Some(EndpointHealth::Failing(_)))
+                && app.worker_pings.is_some()
+        }).await;
+        let app = app_arc.lock().await;
+        assert_eq!(app.worker_statuses.as_ref().unwrap().len(), 3);
+        assert!(app.worker_versions.is_none());
+        assert_eq!(app.connection_status(), ConnectionStatus::Degraded(vec![Resource::Versions]));
+        assert!(app.placeholder(Resource::Versions).contains("versions are admin only"));
+        assert!(app.banners.iter().any(|b| b.starts_with("Can't load versions")));
+    }
+
+    #[tokio::test]
+    async fn drops_results_of_an_old_profile() {
+        let hive = MockHive::start().await.unwrap();
+        hive.script("GET", "/worker/status", [Reply::json(serde_json::json!({})).delayed(std::time::Duration::from_millis(300))]);
+        let app_arc = app_on(&hive, Tab::Dashboard).await;
+        let client = Arc::new(HiveManageClient::new(hive.url(), ADMIN_TOKEN).unwrap());
+        let epoch = app_arc.lock().await.profile_epoch;
+        let _poller = Poller::spawn(client, app_arc.clone(), epoch);
+
+        app_arc.lock().await.profile_epoch += 1;
+        tokio::time::sleep(std::time::Duration::from_millis(600)).await;
+        assert!(app_arc.lock().await.worker_statuses.is_none());
+    }
+}
@@ -0,0 +1,98 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use tokio::sync::MutexGuard;
+
+use crate::app::{App, ProfileEditor, PROFILE_FIELDS};
+
+/// Handle a key while the profiles overlay is open
+pub fn on_profiles_key(app: &mut MutexGuard<'_, App>, key: KeyEvent) {
+    let app: &mut App = app;
+    let Some(view) = app.profiles_view.as_mut() else { return };
+
+    if view.editor.is_some() {
+        on_editor_key(app, key);
+        return;
+    }
+
+    if view.confirm_delete {
+        view.confirm_delete = false;
+        if let KeyCode::Char('y') | KeyCode::Char('Y') = key.code {
+            let index = view.selected;
+            match app.delete_profile(index) {
+                Ok(()) => {
+                    let last = app.profiles.len().saturating_sub(1);
+                    if let Some(view) = app.profiles_view.as_mut() {
+                        view.selected = index.min(last);
+                    }
+                    app.add_banner("Profile deleted.");
+                }
+                Err(e) => app.add_banner(e),
+            }
+        }
+        return;
+    }
+
+    let count = app.profiles.len();
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') => view.selected = view.selected.saturating_sub(1),
+        KeyCode::Down | KeyCode::Char('j') => view.selected = (view.selected + 1).min(count.saturating_sub(1)),
+        KeyCode::Char('a') => view.editor = Some(ProfileEditor::new(None, None)),
+        KeyCode::Char('e') => {
+            let index = view.selected;
+            view.editor = Some(ProfileEditor::new(Some(index), app.profiles.get(index)));
+        }
+        KeyCode::Char('c') => {
+            // Duplicate into a new, unsaved profile with a fresh name
+            let mut editor = ProfileEditor::new(None, app.profiles.get(view.selected));
+            editor.fields[0] = format!("{} copy", editor.fields[0]);
+            view.editor = Some(editor);
+        }
+        KeyCode::Char('d') | KeyCode::Delete => view.confirm_delete = true,
+        KeyCode::Enter => {
+            let index = view.selected;
+            app.profiles_view = None;
+            if index != app.active_profile {
+                app.set_active_profile(index);
+                let msg = format!("Switched to profile '{}'.", app.profiles[index].name);
+                app.add_banner(msg);
+            }
+        }
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::F(4) => app.profiles_view = None,
+        _ => {}
+    }
+}
+
+fn on_editor_key(app: &mut App, key: KeyEvent) {
+    let Some(editor) = app.profiles_view.as_mut().and_then(|v| v.editor.as_mut()) else { return };
+
+    match key.code {
+        KeyCode::Tab | KeyCode::Down => editor.field = (editor.field + 1) % PROFILE_FIELDS.len(),
+        KeyCode::BackTab | KeyCode::Up => {
+            editor.field = (editor.field + PROFILE_FIELDS.len() - 1) % PROFILE_FIELDS.len();
+        }
+        KeyCode::Backspace => {
+            editor.fields[editor.field].pop();
+        }
+        KeyCode::Char(c) => editor.fields[editor.field].push(c),
+        KeyCode::Esc => {
+            if let Some(view) = app.profiles_view.as_mut() {
+                view.editor = None;
+            }
+        }
+        KeyCode::Enter => {
+            let index = editor.index;
+            let result = editor.to_profile()
+                .and_then(|profile| app.save_profile(index, profile));
+            match result {
+                Ok(saved) => {
+                    if let Some(view) = app.profiles_view.as_mut() {
+                        view.editor = None;
+                        view.selected = saved;
+                    }
+                    app.add_banner("Profile saved.");
+                }
+                Err(e) => app.add_banner(e),
+            }
+        }
+        _ => {}
+    }
+}
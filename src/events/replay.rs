@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use chrono::TimeDelta;
+use crossterm::event::{KeyCode, KeyEvent};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, Duration, Instant};
+
+use crate::app::App;
+
+/// How often the replay clock moves on
+const TICK: Duration = Duration::from_millis(100);
+
+/// Plays `App::replay` in the background, standing in for the pollers.
+///
+/// The task runs the replay clock by the wall time that passed since its
+/// last tick, so snapshots show up at the pace they were recorded at
+/// scaled by the playback speed.
+pub struct ReplayPlayer {
+    handle: Option<JoinHandle<()>>,
+}
+
+impl ReplayPlayer {
+    pub fn spawn(app_arc: Arc<Mutex<App>>) -> Self {
+        let handle = tokio::spawn(async move {
+            let mut last = Instant::now();
+            loop {
+                sleep(TICK).await;
+                let now = Instant::now();
+                app_arc.lock().await.step_replay(now - last);
+                last = now;
+            }
+        });
+        ReplayPlayer { handle: Some(handle) }
+    }
+
+    pub fn stop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            handle.abort();
+        }
+    }
+}
+
+impl Drop for ReplayPlayer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Playback controls; returns false for keys that are not one
+pub fn on_replay_key(app: &mut App, key: KeyEvent) -> bool {
+    let Some(replay) = &mut app.replay else { return false };
+    match key.code {
+        KeyCode::Char(' ') => app.toggle_replay(),
+        KeyCode::Char(',') => app.seek_replay(TimeDelta::seconds(-10)),
+        KeyCode::Char('.') => app.seek_replay(TimeDelta::seconds(10)),
+        KeyCode::Char('<') => app.seek_replay(TimeDelta::minutes(-1)),
+        KeyCode::Char('>') => app.seek_replay(TimeDelta::minutes(1)),
+        KeyCode::Char('-') => replay.change_speed(false),
+        KeyCode::Char('+') | KeyCode::Char('=') => replay.change_speed(true),
+        KeyCode::Home => {
+            let by = replay.start() - replay.position;
+            app.seek_replay(by);
+        }
+        KeyCode::End => {
+            let by = replay.end() - replay.position;
+            app.seek_replay(by);
+        }
+        _ => return false,
+    }
+    true
+}
@@ -1,7 +1,7 @@
 
 use tokio::sync::mpsc;
 use tokio::task;
-use tokio::time::{sleep, Duration, Instant};
+use tokio::time::{Duration, Instant};
 use crossterm::event::{self, Event as CEvent, KeyEvent};
 
 /// Wrapper for input and tick events
@@ -15,14 +15,12 @@ pub enum Event {
 /// Event handler producing `Input` and `Tick` events
 pub struct EventSpawner {
     rx: mpsc::Receiver<Event>,
-    tx: mpsc::Sender<Event>,
 }
 
 impl EventSpawner {
     pub fn new() -> Self {
         let (tx, rx) = mpsc::channel(20);
 
-        let tx_cloned = tx.clone();
         tokio::spawn(async move {
             let mut last_tick = Instant::now();
             loop {
@@ -35,42 +33,31 @@ impl EventSpawner {
                     poll_result_handle = crossterm_poll_fut => {
                         if let Ok(Ok(true)) = poll_result_handle {
                             if let Ok(Ok(CEvent::Key(key))) = task::spawn_blocking(event::read).await {
-                                let _ = tx_cloned.send(Event::Input(key)).await;
+                                let _ = tx.send(Event::Input(key)).await;
                             }
                         }
                     },
                     _ = tick_sleep_fut => {
                         if last_tick.elapsed() >= Duration::from_millis(20) {
-                            let _ = tx_cloned.send(Event::Tick).await;
+                            let _ = tx.send(Event::Tick).await;
                             last_tick = Instant::now();
                         }
                     },
                 }
             }
         });
-        Self { rx , tx }
+        Self { rx }
     }
 
-    pub async fn next(&mut self) -> Event {
-        self.rx.recv().await.unwrap_or(Event::Stop)
-    }
-
-    pub fn add_spawn_interval(self, event: Event, interval_duration: Duration) -> Self {
-        let tx = self.tx.clone();
-        tokio::spawn(async move {
-            loop {
-                sleep(interval_duration).await;
-                let _ = tx.send(event.clone()).await;
-            }
-        });
-        self
+    /// A spawner that never reads the terminal; events come only from the
+    /// returned sender, e.g. keys scripted by a test
+    #[cfg(test)]
+    pub fn scripted() -> (Self, mpsc::Sender<Event>) {
+        let (tx, rx) = mpsc::channel(20);
+        (Self { rx }, tx)
     }
 
-    pub fn push_generate_event(&mut self, event: Event, duration: Duration) {
-        let tx = self.tx.clone();
-        tokio::spawn(async move {
-            sleep(duration).await;
-            let _ = tx.send(event).await;  
-        });
+    pub async fn next(&mut self) -> Event {
+        self.rx.recv().await.unwrap_or(Event::Stop)
     }
-}
\ No newline at end of file
+}
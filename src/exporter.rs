@@ -0,0 +1,281 @@
+// src/exporter.rs
+use std::{collections::HashMap, fmt::Write, sync::Arc, time::Duration};
+use chrono::Utc;
+use futures::future::join_all;
+use tokio::net::TcpListener;
+use tokio::sync::Mutex;
+use crate::clients::manage_client::HiveManageClient;
+use crate::config::Profile;
+use crate::errors::ClientError;
+use crate::models::{QueueMap, WorkerConnections, WorkerPings, WorkerStatuses, WorkerVersions};
+use crate::utils::server::{serve, Response};
+
+/// Last scrape of one profile; `None` for endpoints that failed
+#[derive(Debug, Default, Clone)]
+pub struct ClusterMetrics {
+    pub scrape_seconds: f64,
+    pub statuses: Option<WorkerStatuses>,
+    pub connections: Option<WorkerConnections>,
+    pub pings: Option<WorkerPings>,
+    pub versions: Option<WorkerVersions>,
+    pub queue: Option<QueueMap>,
+}
+
+impl ClusterMetrics {
+    /// Whether each endpoint answered, by endpoint label
+    fn endpoints(&self) -> [(&'static str, bool); 5] {
+        [
+            ("status", self.statuses.is_some()),
+            ("connections", self.connections.is_some()),
+            ("pings", self.pings.is_some()),
+            ("versions", self.versions.is_some()),
+            ("queue", self.queue.is_some()),
+        ]
+    }
+}
+
+/// Poll `profiles` every `interval` and serve the results on `/metrics`
+pub async fn run(profiles: Vec<Profile>, listen: &str, interval: Duration) -> Result<(), ClientError> {
+    let mut clients = Vec::new();
+    for profile in profiles {
+        let client = HiveManageClient::new(
+            format!("{}:{}", profile.host, profile.port_manage),
+            &profile.admin_token,
+        )?;
+        clients.push((profile.name, Arc::new(client)));
+    }
+
+    let metrics: Arc<Mutex<HashMap<String, ClusterMetrics>>> = Arc::new(Mutex::new(HashMap::new()));
+
+    let poll_metrics = metrics.clone();
+    tokio::spawn(async move {
+        loop {
+            let results = join_all(clients.iter().map(|(name, client)| async move {
+                (name.clone(), scrape(client).await)
+            })).await;
+            let mut metrics = poll_metrics.lock().await;
+            for (name, result) in results {
+                metrics.insert(name, result);
+            }
+            drop(metrics);
+            tokio::time::sleep(interval).await;
+        }
+    });
+
+    let listener = TcpListener::bind(listen).await?;
+    eprintln!("Serving HiveCore metrics on http://{}/metrics", listener.local_addr()?);
+    serve(listener, move |request| {
+        let metrics = metrics.clone();
+        async move {
+            match (request.method.as_str(), request.path.as_str()) {
+                ("GET", "/metrics") => {
+                    let body = render(&*metrics.lock().await);
+                    Response::new(200, "text/plain; version=0.0.4; charset=utf-8", body)
+                }
+                ("GET", "/") => Response::new(200, "text/plain", "HiveCore exporter, see /metrics\n"),
+                _ => Response::not_found(),
+            }
+        }
+    }).await;
+    Ok(())
+}
+
+/// Fetch every endpoint on its own, so one failing keeps none of the others out
+async fn scrape(client: &HiveManageClient) -> ClusterMetrics {
+    let started = std::time::Instant::now();
+    let (statuses, connections, pings, versions, queue) = tokio::join!(
+        client.get_worker_status(),
+        client.get_worker_connections(),
+        client.get_worker_pings(),
+        client.get_worker_versions(),
+        client.get_queue(),
+    );
+    ClusterMetrics {
+        scrape_seconds: started.elapsed().as_secs_f64(),
+        statuses: statuses.ok(),
+        connections: connections.ok(),
+        pings: pings.ok(),
+        versions: versions.ok(),
+        queue: queue.ok(),
+    }
+}
+
+/// Render every profile's metrics in the Prometheus text exposition format
+pub fn render(clusters: &HashMap<String, ClusterMetrics>) -> String {
+    let mut profiles: Vec<&String> = clusters.keys().collect();
+    profiles.sort();
+    let now = Utc::now();
+
+    let mut families: Vec<(&str, &str, &str, Vec<String>)> = vec![
+        ("hivecore_up", "gauge", "Whether any endpoint of the management API answered the last scrape", Vec::new()),
+        ("hivecore_scrape_duration_seconds", "gauge", "Duration of the last scrape", Vec::new()),
+        ("hivecore_endpoint_up", "gauge", "Whether the endpoint answered the last scrape", Vec::new()),
+        ("hivecore_workers", "gauge", "Authenticated workers", Vec::new()),
+        ("hivecore_worker_connections", "gauge", "Open connections per worker", Vec::new()),
+        ("hivecore_worker_busy", "gauge", "1 if the worker's last status is Working", Vec::new()),
+        ("hivecore_worker_status", "gauge", "Last reported worker status, always 1", Vec::new()),
+        ("hivecore_worker_last_ping_age_seconds", "gauge", "Seconds since the worker last pinged", Vec::new()),
+        ("hivecore_worker_info", "gauge", "Worker software versions, always 1", Vec::new()),
+        ("hivecore_queue_length", "gauge", "Queued requests per model", Vec::new()),
+        ("hivecore_node_queue_length", "gauge", "Queued requests pinned to a worker", Vec::new()),
+    ];
+    let mut push = |family: usize, labels: &[(&str, &str)], value: f64| {
+        let labels: Vec<String> = labels.iter()
+            .map(|(k, v)| format!("{}=\"{}\"", k, escape(v)))
+            .collect();
+        let name = families[family].0;
+        families[family].3.push(format!("{}{{{}}} {}", name, labels.join(","), value));
+    };
+
+    for profile in profiles {
+        let m = &clusters[profile];
+        let p = profile.as_str();
+        let endpoints = m.endpoints();
+        push(0, &[("profile", p)], if endpoints.iter().any(|(_, up)| *up) { 1.0 } else { 0.0 });
+        push(1, &[("profile", p)], m.scrape_seconds);
+        for (endpoint, up) in endpoints {
+            push(2, &[("profile", p), ("endpoint", endpoint)], if up { 1.0 } else { 0.0 });
+        }
+
+        if let Some(statuses) = &m.statuses {
+            let workers = sorted_workers(statuses);
+            push(3, &[("profile", p)], workers.len() as f64);
+            for worker in workers {
+                let w = worker.as_str();
+                let status = statuses[worker].last().map(String::as_str).unwrap_or("Unknown");
+                push(5, &[("profile", p), ("worker", w)], if status == "Working" { 1.0 } else { 0.0 });
+                push(6, &[("profile", p), ("worker", w), ("status", status)], 1.0);
+            }
+        }
+        if let Some(connections) = &m.connections {
+            for worker in sorted_workers(connections) {
+                push(4, &[("profile", p), ("worker", worker)], connections[worker] as f64);
+            }
+        }
+        if let Some(pings) = &m.pings {
+            for worker in sorted_workers(pings) {
+                if let Some(last) = pings[worker].last() {
+                    push(7, &[("profile", p), ("worker", worker)], (now - *last).num_milliseconds() as f64 / 1000.0);
+                }
+            }
+        }
+        if let Some(versions) = &m.versions {
+            for worker in sorted_workers(versions) {
+                let v = &versions[worker];
+                push(8, &[("profile", p), ("worker", worker), ("hive_version", &v.hive), ("ollama_version", &v.ollama)], 1.0);
+            }
+        }
+
+        let mut queues: Vec<(&String, &usize)> = m.queue.iter().flatten().collect();
+        queues.sort();
+        for (key, count) in queues {
+            match key.strip_prefix("Node:") {
+                Some(worker) => push(10, &[("profile", p), ("worker", worker.trim())], *count as f64),
+                None => push(9, &[("profile", p), ("model", key.trim_start_matches("Model:").trim())], *count as f64),
+            }
+        }
+    }
+
+    let mut out = String::new();
+    for (name, kind, help, samples) in families {
+        if samples.is_empty() {
+            continue;
+        }
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        let _ = writeln!(out, "# TYPE {} {}", name, kind);
+        for sample in samples {
+            let _ = writeln!(out, "{}", sample);
+        }
+    }
+    out
+}
+
+/// Authenticated workers of a per-worker map, sorted by name
+fn sorted_workers<T>(map: &HashMap<String, T>) -> Vec<&String> {
+    let mut workers: Vec<&String> = map.keys().filter(|w| *w != "Unauthenticated").collect();
+    workers.sort();
+    workers
+}
+
+/// Escape a label value per the exposition format
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn cluster() -> ClusterMetrics {
+        ClusterMetrics {
+            scrape_seconds: 0.5,
+            statuses: serde_json::from_value(json!({
+                "gpu-01": ["Polling", "Working"],
+                "lab \"b\"\\2": ["Polling"],
+                "Unauthenticated": [],
+            })).unwrap(),
+            connections: serde_json::from_value(json!({ "gpu-01": 2, "Unauthenticated": 1 })).unwrap(),
+            pings: serde_json::from_value(json!({ "gpu-01": [Utc::now().to_rfc3339()] })).unwrap(),
+            versions: serde_json::from_value(json!({ "gpu-01": { "hive": "0.4.2", "ollama": "0.6.5" } })).unwrap(),
+            queue: serde_json::from_value(json!({ "Model: llama3.1:8b": 3, "Node: gpu-01": 1 })).unwrap(),
+        }
+    }
+
+    #[test]
+    fn renders_every_family_with_its_labels() {
+        let out = render(&HashMap::from([("lab".to_string(), cluster())]));
+        for line in [
+            "# TYPE hivecore_up gauge",
+            "hivecore_up{profile=\"lab\"} 1",
+            "hivecore_scrape_duration_seconds{profile=\"lab\"} 0.5",
+            "hivecore_endpoint_up{profile=\"lab\",endpoint=\"pings\"} 1",
+            "hivecore_workers{profile=\"lab\"} 2",
+            "hivecore_worker_connections{profile=\"lab\",worker=\"gpu-01\"} 2",
+            "hivecore_worker_busy{profile=\"lab\",worker=\"gpu-01\"} 1",
+            "hivecore_worker_status{profile=\"lab\",worker=\"gpu-01\",status=\"Working\"} 1",
+            "hivecore_worker_info{profile=\"lab\",worker=\"gpu-01\",hive_version=\"0.4.2\",ollama_version=\"0.6.5\"} 1",
+            "hivecore_queue_length{profile=\"lab\",model=\"llama3.1:8b\"} 3",
+            "hivecore_node_queue_length{profile=\"lab\",worker=\"gpu-01\"} 1",
+        ] {
+            assert!(out.lines().any(|l| l == line), "missing {:?} in\n{}", line, out);
+        }
+        assert!(out.contains("hivecore_worker_last_ping_age_seconds{profile=\"lab\",worker=\"gpu-01\"} "));
+        assert!(!out.contains("Unauthenticated"));
+    }
+
+    #[test]
+    fn escapes_label_values() {
+        let out = render(&HashMap::from([("lab".to_string(), cluster())]));
+        assert!(out.contains(r#"hivecore_worker_busy{profile="lab",worker="lab \"b\"\\2"} 0"#), "{}", out);
+        assert_eq!(escape("a\nb"), "a\\nb");
+    }
+
+    #[test]
+    fn a_failed_endpoint_only_drops_its_own_metrics() {
+        let cluster = ClusterMetrics { pings: None, ..cluster() };
+        let out = render(&HashMap::from([("lab".to_string(), cluster)]));
+        assert!(out.lines().any(|l| l == "hivecore_up{profile=\"lab\"} 1"));
+        assert!(out.lines().any(|l| l == "hivecore_endpoint_up{profile=\"lab\",endpoint=\"pings\"} 0"));
+        assert!(out.lines().any(|l| l == "hivecore_endpoint_up{profile=\"lab\",endpoint=\"status\"} 1"));
+        assert!(out.contains("hivecore_worker_connections{"));
+        assert!(!out.contains("hivecore_worker_last_ping_age_seconds"));
+    }
+
+    #[test]
+    fn a_failed_scrape_reports_every_endpoint_down() {
+        let down = ClusterMetrics { scrape_seconds: 2.0, ..ClusterMetrics::default() };
+        let out = render(&HashMap::from([("lab".to_string(), down)]));
+        let samples: Vec<&str> = out.lines().filter(|l| !l.starts_with('#')).collect();
+        assert_eq!(samples, [
+            "hivecore_up{profile=\"lab\"} 0",
+            "hivecore_scrape_duration_seconds{profile=\"lab\"} 2",
+            "hivecore_endpoint_up{profile=\"lab\",endpoint=\"status\"} 0",
+            "hivecore_endpoint_up{profile=\"lab\",endpoint=\"connections\"} 0",
+            "hivecore_endpoint_up{profile=\"lab\",endpoint=\"pings\"} 0",
+            "hivecore_endpoint_up{profile=\"lab\",endpoint=\"versions\"} 0",
+            "hivecore_endpoint_up{profile=\"lab\",endpoint=\"queue\"} 0",
+        ]);
+    }
+}
@@ -0,0 +1,265 @@
+// src/history.rs
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use std::{collections::{HashMap, VecDeque}, fs, path::PathBuf};
+use crate::config::project_dirs;
+use crate::errors::ClientError;
+use crate::models::{QueueMap, WorkerConnections, WorkerStatuses};
+
+/// Samples kept per series (one hour at `MIN_SPACING_SECS`)
+pub const MAX_SAMPLES: usize = 1800;
+/// Polls closer than this to the last sample replace it instead of adding one
+pub const MIN_SPACING_SECS: i64 = 2;
+/// Status transitions kept across all workers
+pub const MAX_TRANSITIONS: usize = 500;
+
+/// One value of a series at a point in time
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Sample {
+    pub at: DateTime<Utc>,
+    pub value: u64,
+}
+
+/// A worker's status changing between two polls
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusTransition {
+    pub at: DateTime<Utc>,
+    pub worker: String,
+    /// `None` when the worker was first seen
+    pub from: Option<String>,
+    /// `None` when the worker disappeared
+    pub to: Option<String>,
+}
+
+/// Bounded time series of the polled cluster state
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct History {
+    /// Connections per worker
+    pub connections: HashMap<String, VecDeque<Sample>>,
+    /// Queue length per queue key ("Model: x", "Node: y")
+    pub queues: HashMap<String, VecDeque<Sample>>,
+    /// Connections summed over authenticated workers
+    pub total_connections: VecDeque<Sample>,
+    /// Queued requests summed over model queues
+    pub total_queued: VecDeque<Sample>,
+    pub transitions: VecDeque<StatusTransition>,
+    last_status: HashMap<String, String>,
+}
+
+impl History {
+    pub fn record_connections(&mut self, at: DateTime<Utc>, connections: &WorkerConnections) {
+        for (worker, count) in connections {
+            push(self.connections.entry(worker.clone()).or_default(), at, *count as u64);
+        }
+        let total = connections.iter()
+            .filter(|(n, _)| *n != "Unauthenticated")
+            .map(|(_, c)| *c as u64)
+            .sum();
+        push(&mut self.total_connections, at, total);
+    }
+
+    pub fn record_queue(&mut self, at: DateTime<Utc>, queue: &QueueMap) {
+        // Queues that emptied out disappear from the map, record them as 0
+        for (name, series) in self.queues.iter_mut() {
+            if !queue.contains_key(name) {
+                push(series, at, 0);
+            }
+        }
+        for (name, count) in queue {
+            push(self.queues.entry(name.clone()).or_default(), at, *count as u64);
+        }
+        let total = queue.iter()
+            .filter(|(k, _)| !k.starts_with("Node:"))
+            .map(|(_, c)| *c as u64)
+            .sum();
+        push(&mut self.total_queued, at, total);
+    }
+
+    pub fn record_statuses(&mut self, at: DateTime<Utc>, statuses: &WorkerStatuses) {
+        for (worker, status) in statuses {
+            if worker == "Unauthenticated" {
+                continue;
+            }
+            let Some(status) = status.last() else { continue };
+            let previous = self.last_status.insert(worker.clone(), status.clone());
+            if previous.as_ref() != Some(status) {
+                self.push_transition(at, worker.clone(), previous, Some(status.clone()));
+            }
+        }
+        let gone: Vec<String> = self.last_status.keys()
+            .filter(|w| !statuses.contains_key(*w))
+            .cloned()
+            .collect();
+        for worker in gone {
+            let previous = self.last_status.remove(&worker);
+            self.push_transition(at, worker, previous, None);
+        }
+    }
+
+    /// Latest transitions of `worker`, newest first
+    pub fn transitions_of<'a>(&'a self, worker: &'a str) -> impl Iterator<Item = &'a StatusTransition> + 'a {
+        self.transitions.iter().rev().filter(move |t| t.worker == worker)
+    }
+
+    fn push_transition(&mut self, at: DateTime<Utc>, worker: String, from: Option<String>, to: Option<String>) {
+        if self.transitions.len() == MAX_TRANSITIONS {
+            self.transitions.pop_front();
+        }
+        self.transitions.push_back(StatusTransition { at, worker, from, to });
+    }
+
+    /// Load the stored history of a profile, or an empty one if none exists
+    pub fn load(profile: &str) -> Result<Self, ClientError> {
+        let path = history_path(profile)?;
+        if !path.exists() {
+            return Ok(History::default());
+        }
+        let contents = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Store this history for a profile
+    pub fn save(&self, profile: &str) -> Result<(), ClientError> {
+        let path = history_path(profile)?;
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+/// The values of the last `n` samples, oldest first, e.g. for a sparkline
+pub fn tail_values(series: &VecDeque<Sample>, n: usize) -> Vec<u64> {
+    series.iter().skip(series.len().saturating_sub(n)).map(|s| s.value).collect()
+}
+
+fn push(series: &mut VecDeque<Sample>, at: DateTime<Utc>, value: u64) {
+    if let Some(last) = series.back_mut() {
+        if (at - last.at).num_seconds() < MIN_SPACING_SECS {
+            last.value = value;
+            return;
+        }
+    }
+    if series.len() == MAX_SAMPLES {
+        series.pop_front();
+    }
+    series.push_back(Sample { at, value });
+}
+
+/// Returns the history file of a profile, creating directories if needed
+fn history_path(profile: &str) -> Result<PathBuf, ClientError> {
+    let dir = project_dirs()?.data_dir().join("history");
+    fs::create_dir_all(&dir)?;
+    Ok(dir.join(history_file_name(profile)))
+}
+
+/// File name for a profile's history. Bytes other than letters, digits,
+/// `-` and `_` are percent-encoded, so distinct names never share a file.
+fn history_file_name(profile: &str) -> String {
+    let mut file = String::new();
+    for byte in profile.bytes() {
+        if byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_' {
+            file.push(byte as char);
+        } else {
+            file.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    file + ".json"
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+
+    use super::*;
+
+    fn at(seconds: i64) -> DateTime<Utc> {
+        DateTime::UNIX_EPOCH + TimeDelta::seconds(seconds)
+    }
+
+    fn statuses(entries: &[(&str, &str)]) -> WorkerStatuses {
+        entries.iter().map(|(w, s)| (w.to_string(), vec![s.to_string()])).collect()
+    }
+
+    #[test]
+    fn close_polls_replace_the_last_sample() {
+        let mut series = VecDeque::new();
+        push(&mut series, at(0), 1);
+        push(&mut series, at(1), 2);
+        push(&mut series, at(MIN_SPACING_SECS), 3);
+        let samples: Vec<(DateTime<Utc>, u64)> = series.iter().map(|s| (s.at, s.value)).collect();
+        assert_eq!(samples, [(at(0), 2), (at(MIN_SPACING_SECS), 3)]);
+    }
+
+    #[test]
+    fn series_keep_the_latest_samples() {
+        let mut series = VecDeque::new();
+        for i in 0..MAX_SAMPLES as i64 + 5 {
+            push(&mut series, at(i * MIN_SPACING_SECS), i as u64);
+        }
+        assert_eq!(series.len(), MAX_SAMPLES);
+        assert_eq!(series.front().unwrap().value, 5);
+        assert_eq!(tail_values(&series, 2), [MAX_SAMPLES as u64 + 3, MAX_SAMPLES as u64 + 4]);
+    }
+
+    #[test]
+    fn totals_skip_unauthenticated_and_node_queues() {
+        let mut history = History::default();
+        let connections = WorkerConnections::from([("gpu-01".into(), 2), ("gpu-02".into(), 1), ("Unauthenticated".into(), 4)]);
+        history.record_connections(at(0), &connections);
+        let queue = QueueMap::from([("Model: llama3".into(), 3), ("Node: gpu-01".into(), 2)]);
+        history.record_queue(at(0), &queue);
+        assert_eq!(tail_values(&history.total_connections, 1), [3]);
+        assert_eq!(tail_values(&history.total_queued, 1), [3]);
+        assert_eq!(tail_values(&history.connections["Unauthenticated"], 1), [4]);
+    }
+
+    #[test]
+    fn emptied_queues_drop_to_zero() {
+        let mut history = History::default();
+        history.record_queue(at(0), &QueueMap::from([("Model: llama3".into(), 3)]));
+        history.record_queue(at(10), &QueueMap::new());
+        assert_eq!(tail_values(&history.queues["Model: llama3"], 2), [3, 0]);
+        assert_eq!(tail_values(&history.total_queued, 2), [3, 0]);
+    }
+
+    #[test]
+    fn status_changes_are_recorded_as_transitions() {
+        let mut history = History::default();
+        history.record_statuses(at(0), &statuses(&[("gpu-01", "Polling"), ("Unauthenticated", "Polling")]));
+        history.record_statuses(at(10), &statuses(&[("gpu-01", "Polling")]));
+        history.record_statuses(at(20), &statuses(&[("gpu-01", "Working")]));
+        history.record_statuses(at(30), &statuses(&[]));
+
+        let seen: Vec<(i64, Option<&str>, Option<&str>)> = history.transitions_of("gpu-01")
+            .map(|t| (t.at.timestamp(), t.from.as_deref(), t.to.as_deref()))
+            .collect();
+        assert_eq!(seen, [
+            (30, Some("Working"), None),
+            (20, Some("Polling"), Some("Working")),
+            (0, None, Some("Polling")),
+        ]);
+        assert_eq!(history.transitions.len(), 3);
+    }
+
+    #[test]
+    fn profile_names_map_to_distinct_files() {
+        assert_eq!(history_file_name("lab-1_a"), "lab-1_a.json");
+        assert_eq!(history_file_name("lab 1"), "lab%201.json");
+        assert_eq!(history_file_name("prod.eu"), "prod%2Eeu.json");
+        assert_eq!(history_file_name("../x"), "%2E%2E%2Fx.json");
+        assert_eq!(history_file_name("č"), "%C4%8D.json");
+        let names = ["lab 1", "lab_1", "lab%201", "prod.eu", "prod_eu"];
+        let files: std::collections::HashSet<String> = names.iter().map(|n| history_file_name(n)).collect();
+        assert_eq!(files.len(), names.len());
+    }
+
+    #[test]
+    fn transitions_keep_the_latest() {
+        let mut history = History::default();
+        for i in 0..MAX_TRANSITIONS as i64 + 10 {
+            let status = if i % 2 == 0 { "Polling" } else { "Working" };
+            history.record_statuses(at(i), &statuses(&[("gpu-01", status)]));
+        }
+        assert_eq!(history.transitions.len(), MAX_TRANSITIONS);
+        assert_eq!(history.transitions.front().unwrap().at, at(10));
+    }
+}
@@ -3,29 +3,78 @@ mod clients;
 mod config;
 mod errors;
 mod models;
+mod history;
+mod alerts;
+mod notify;
+mod exporter;
 mod app;
 mod ui;
 mod events;
+mod cli;
+mod mock;
+mod recording;
 
 use std::sync::Arc;
 use std::thread::sleep;
 use tokio::sync::Mutex;
 use tokio::time::Duration;
 use anyhow::Result;
+use clap::Parser;
 
-use crate::app::{App, Tab};
-use crate::clients::manage_client::HiveManageClient;
+use crate::app::App;
+use crate::cli::Cli;
 use crate::config::{load_profiles, save_profiles, Profile};
 use crate::errors::ClientError;
 use crate::events::handler::handle_events;
-use crate::events::spawner::{Event, EventSpawner};
+use crate::events::spawner::EventSpawner;
 use crate::ui::terminal;
-use crate::ui::tabs;
+use crate::utils::retry::RequestPolicy;
 
 #[tokio::main]
 async fn main() -> Result<(), ClientError> {
-    // Load or initialize profiles
-    let mut profiles = load_profiles()?;
+    let args = Cli::parse();
+    RequestPolicy::install(RequestPolicy {
+        timeout: Duration::from_secs(args.timeout),
+        retries: args.retries,
+        ..RequestPolicy::default()
+    });
+
+    // Load or initialize profiles; the demo brings its own and must stay up until exit
+    let demo = if args.demo { Some(mock::MockHive::demo().await?) } else { None };
+    let replay = match &args.replay {
+        Some(path) => match recording::Replay::load(path) {
+            Ok(replay) => Some(replay),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(cli::EXIT_CONFIG_ERROR);
+            }
+        },
+        None => None,
+    };
+    let mut profiles = match (&demo, &replay) {
+        (Some(hive), _) => vec![hive.profile("demo")],
+        // Never contacted: the replay task stands in for the pollers
+        (_, Some(replay)) => vec![Profile::single_port(&replay.profile, "", 0)],
+        _ => load_profiles()?,
+    };
+
+    // Non-interactive subcommands never prompt and never touch the terminal
+    if let Some(cli::Command::ServeMetrics { listen, interval }) = &args.command {
+        let code = cli::serve_metrics(profiles, args.profile.as_deref(), listen, *interval).await;
+        std::process::exit(code);
+    }
+    if let Some(cli::Command::Profile(command)) = args.command {
+        let Some(index) = cli::select_profile(&profiles, args.profile.as_deref()) else {
+            match &args.profile {
+                Some(name) => eprintln!("Error: no profile named '{}'", name),
+                None => eprintln!("Error: no HiveCore profiles configured; run without a subcommand to create one"),
+            }
+            std::process::exit(cli::EXIT_CONFIG_ERROR);
+        };
+        let code = cli::run(command, &profiles[index], args.json).await;
+        std::process::exit(code);
+    }
+
     // If no profiles exist, create one interactively
     if profiles.is_empty() {
         use std::io::{stdin, stdout, Write};
@@ -69,24 +118,66 @@ async fn main() -> Result<(), ClientError> {
         profiles = vec![new_profile];
     }
 
+    let active_profile = match cli::select_profile(&profiles, args.profile.as_deref()) {
+        Some(index) => index,
+        None => {
+            eprintln!("[Error] No profile named '{}'", args.profile.unwrap_or_default());
+            std::process::exit(cli::EXIT_CONFIG_ERROR);
+        }
+    };
     let mut app = App::new(profiles);
-
-    if let Err(e) = init_app_data(&mut app).await {
-        println!("[Error] Can't init app data: {:#?}", e);
-        return Err(e);
+    app.persist_history = args.persist_history;
+    app.persist_profiles = demo.is_none() && replay.is_none();
+    app.action_parallelism = args.parallel as usize;
+    match alerts::load_rules() {
+        Ok(rules) => app.alerts = alerts::AlertEngine::new(rules),
+        Err(e) => app.add_banner(format!("Can't load alerts.toml: {}", e)),
+    }
+    // Data arrives from the background pollers, so an unreachable HiveCore
+    // shows up in the connection status instead of stopping the start
+    app.set_active_profile(active_profile);
+    if let Some(path) = &args.record {
+        match recording::Recorder::create(path) {
+            Ok(recorder) => app.recorder = Some(recorder),
+            Err(e) => app.add_banner(format!("Can't record to {}: {}", path.display(), e)),
+        }
+    }
+    if let Some(replay) = replay {
+        if replay.skipped > 0 {
+            app.add_banner(format!(
+                "{} holds several profiles; replaying {} and leaving out {} snapshots",
+                replay.path.display(), replay.profile, replay.skipped,
+            ));
+        }
+        app.replay = Some(replay);
+        app.step_replay(Duration::ZERO);
     }
-
 
     let app_arc = Arc::new(Mutex::new(app));
+
+    // Notification sinks report delivery problems as banners; a replay
+    // only shows its alerts
+    match notify::load_sinks() {
+        _ if args.replay.is_some() => {}
+        Ok(sinks) if !sinks.is_empty() => {
+            let app_for_report = app_arc.clone();
+            let notifier = notify::Notifier::spawn(sinks, move |msg| {
+                let app_arc = app_for_report.clone();
+                tokio::spawn(async move { app_arc.lock().await.add_banner(msg) });
+            });
+            app_arc.lock().await.notifier = notifier;
+        }
+        Ok(_) => {}
+        Err(e) => app_arc.lock().await.add_banner(format!("Can't load notification sinks: {}", e)),
+    }
     let should_stop = Arc::new(Mutex::new(false));
 
     
     // Terminal setup
     let mut terminal = terminal::setup_terminal()?;
 
-    // Event handling (keyboard + tick)
-    let event_spawner = EventSpawner::new()
-        .add_spawn_interval(Event::Tick, Duration::from_secs(5));
+    // Event handling (keyboard + tick); data polling runs on its own tasks
+    let event_spawner = EventSpawner::new();
 
 
     let app_arc_clone = app_arc.clone();
@@ -102,13 +193,7 @@ async fn main() -> Result<(), ClientError> {
 
         {
             let app = app_arc.lock().await;
-            terminal.draw(|f| {
-                match app.current_tab {
-                    Tab::Dashboard => tabs::dashboard::draw(f, &app),
-                    Tab::Console => tabs::console::draw(f, &app),
-                }
-                ui::terminal::draw_banners(f, &app.banners);
-            })?;
+            terminal.draw(|f| ui::draw(f, &app))?;
         }
 
         sleep(Duration::from_millis(50));
@@ -117,24 +202,7 @@ async fn main() -> Result<(), ClientError> {
         }
     }
 
+    app_arc.lock().await.save_history();
     terminal::restore_terminal()?;
     Ok(())
 }
-
-async fn init_app_data(app: &mut App) -> Result<(), ClientError> {
-    let client = HiveManageClient::new(
-        format!("{}:{}", 
-            app.profiles[app.active_profile].host, 
-            app.profiles[app.active_profile].port_manage
-        ),
-        &app.profiles[app.active_profile].admin_token,
-    )?;
-    app.worker_statuses = Some(client.get_worker_status().await?);
-    app.worker_connections = Some(client.get_worker_connections().await?);
-    app.worker_pings = Some(client.get_worker_pings().await?);
-    app.worker_versions = Some(client.get_worker_versions().await?);
-    app.worker_tags = Some(client.get_worker_tags().await?);
-    app.queue_map = Some(client.get_queue().await?);
-    app.auth_keys = Some(client.get_keys().await?);
-    Ok(())
-}
@@ -0,0 +1,437 @@
+// src/mock.rs
+use std::collections::{BTreeMap, BTreeSet};
+#[cfg(test)]
+use std::collections::{HashMap, VecDeque};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use chrono::Utc;
+use rand::Rng;
+use serde_json::{json, Value};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+use crate::config::Profile;
+use crate::utils::server::{serve, Request, Response};
+
+/// Token the mock accepts on the inference API
+pub const CLIENT_TOKEN: &str = "mock-client-token";
+/// Token the mock accepts on the management API (and the inference API)
+pub const ADMIN_TOKEN: &str = "mock-admin-token";
+
+/// Pause between streamed lines in demo mode
+const DEMO_PACE: Duration = Duration::from_millis(120);
+/// Seconds between the pings reported for each worker
+const PING_EVERY_SECS: i64 = 5;
+
+/// One fake worker
+#[derive(Debug, Clone)]
+struct Worker {
+    statuses: Vec<String>,
+    connections: usize,
+    hive: String,
+    ollama: String,
+    models: BTreeSet<String>,
+}
+
+/// Everything the mock serves; pulls, deletes and new keys change it
+#[derive(Debug)]
+struct State {
+    workers: BTreeMap<String, Worker>,
+    queue: BTreeMap<String, usize>,
+    keys: Vec<Value>,
+    /// Pause between lines of streamed responses
+    pace: Option<Duration>,
+    /// Vary queue lengths and busy workers on every request
+    live: bool,
+    /// Replies queued per (method, path), answered before the defaults
+    #[cfg(test)]
+    scripts: HashMap<(String, String), VecDeque<Reply>>,
+    /// Every request received, oldest first
+    #[cfg(test)]
+    requests: Vec<Request>,
+}
+
+/// An in-process fake HiveCore serving both the management and the
+/// inference API on one port.
+///
+/// It starts with three workers and answers `/queue`, `/worker/*`, `/key`,
+/// `/api/generate`, `/api/chat`, `/api/models`, `/api/pull` (NDJSON) and
+/// `/api/delete` like HiveCore does. Tests can queue scripted replies for a
+/// route to produce errors, slow answers and malformed bodies. The server
+/// stops when the value is dropped.
+pub struct MockHive {
+    addr: SocketAddr,
+    #[cfg(test)]
+    state: Arc<Mutex<State>>,
+    task: JoinHandle<()>,
+}
+
+impl MockHive {
+    /// Start a mock with fixed data that answers without delay
+    #[cfg(test)]
+    pub async fn start() -> std::io::Result<MockHive> {
+        MockHive::spawn(State::fixture()).await
+    }
+
+    /// Start a mock for `--demo`: queues and busy workers change between
+    /// polls and streamed responses arrive at a readable pace
+    pub async fn demo() -> std::io::Result<MockHive> {
+        MockHive::spawn(State { pace: Some(DEMO_PACE), live: true, ..State::fixture() }).await
+    }
+
+    async fn spawn(state: State) -> std::io::Result<MockHive> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(state));
+        let shared = state.clone();
+        let task = tokio::spawn(serve(listener, move |request| {
+            let state = shared.clone();
+            async move { answer(&state, request).await }
+        }));
+        Ok(MockHive {
+            addr,
+            #[cfg(test)]
+            state,
+            task,
+        })
+    }
+
+    /// A profile pointing both APIs at the mock
+    pub fn profile(&self, name: &str) -> Profile {
+        Profile::single_port(name, "http://127.0.0.1", self.addr.port()).with_tokens(CLIENT_TOKEN, ADMIN_TOKEN)
+    }
+
+    /// Base URL of the mock, e.g. "http://127.0.0.1:40123"
+    #[cfg(test)]
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Answer the next requests to `path` with `replies`, in order. The
+    /// last reply keeps being used until the route is scripted again.
+    #[cfg(test)]
+    pub fn script(&self, method: &str, path: &str, replies: impl IntoIterator<Item = Reply>) {
+        let replies: VecDeque<Reply> = replies.into_iter().collect();
+        lock(&self.state).scripts.insert((method.to_string(), path.to_string()), replies);
+    }
+
+    /// Requests received so far, oldest first
+    #[cfg(test)]
+    pub fn requests(&self) -> Vec<Request> {
+        lock(&self.state).requests.clone()
+    }
+
+    /// Models the mock currently has on `worker`
+    #[cfg(test)]
+    pub fn models_on(&self, worker: &str) -> Vec<String> {
+        lock(&self.state).workers.get(worker).map(|w| w.models.iter().cloned().collect()).unwrap_or_default()
+    }
+}
+
+impl Drop for MockHive {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// A scripted answer for one request
+#[cfg(test)]
+#[derive(Debug, Clone)]
+pub struct Reply {
+    response: Response,
+    /// Wait this long before answering
+    delay: Duration,
+}
+
+#[cfg(test)]
+impl Reply {
+    pub fn json(value: Value) -> Self {
+        Reply::raw(200, "application/json", value.to_string())
+    }
+
+    /// An error status with a plain text body
+    pub fn status(status: u16, body: &str) -> Self {
+        Reply::raw(status, "text/plain", body)
+    }
+
+    /// Any body, e.g. one that is not valid JSON
+    pub fn raw(status: u16, content_type: &str, body: impl Into<Vec<u8>>) -> Self {
+        Reply { response: Response::new(status, content_type, body), delay: Duration::ZERO }
+    }
+
+    /// A 200 NDJSON stream with one line per value
+    pub fn ndjson(lines: &[Value]) -> Self {
+        Reply::raw(200, "application/x-ndjson", ndjson_body(lines))
+    }
+
+    /// Answer only after `delay`
+    pub fn delayed(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Send the body line by line, `delay` apart
+    pub fn paced(mut self, delay: Duration) -> Self {
+        self.response = self.response.paced(delay);
+        self
+    }
+}
+
+impl State {
+    fn fixture() -> State {
+        let worker = |statuses: &[&str], connections, models: &[&str]| Worker {
+            statuses: statuses.iter().map(|s| s.to_string()).collect(),
+            connections,
+            hive: "0.4.2".to_string(),
+            ollama: "0.6.5".to_string(),
+            models: models.iter().map(|m| m.to_string()).collect(),
+        };
+        let workers = BTreeMap::from([
+            ("gpu-01".to_string(), worker(&["Polling", "Working"], 2, &["llama3.1:8b", "qwen2.5:7b"])),
+            ("gpu-02".to_string(), worker(&["Polling"], 1, &["llama3.1:8b"])),
+            ("cpu-01".to_string(), worker(&["Polling"], 1, &["nomic-embed-text:latest"])),
+        ]);
+        let queue = BTreeMap::from([
+            ("Model: llama3.1:8b".to_string(), 2),
+            ("Model: qwen2.5:7b".to_string(), 0),
+            ("Node: gpu-01".to_string(), 1),
+        ]);
+        let keys = vec![
+            json!({ "id": "1", "name": "admin", "role": "Admin", "value": ADMIN_TOKEN }),
+            json!({ "id": "2", "name": "client", "role": "Client", "value": CLIENT_TOKEN }),
+        ];
+        State {
+            workers,
+            queue,
+            keys,
+            pace: None,
+            live: false,
+            #[cfg(test)]
+            scripts: HashMap::new(),
+            #[cfg(test)]
+            requests: Vec::new(),
+        }
+    }
+
+    /// Shuffle queue lengths and busy workers a little, for the demo
+    fn vary(&mut self) {
+        let mut rng = rand::rng();
+        for count in self.queue.values_mut() {
+            *count = rng.random_range(0..6);
+        }
+        for worker in self.workers.values_mut() {
+            let busy = rng.random_bool(0.4);
+            worker.statuses = if busy { vec!["Polling".into(), "Working".into()] } else { vec!["Polling".into()] };
+        }
+    }
+
+    fn all_models(&self) -> BTreeSet<String> {
+        self.workers.values().flat_map(|w| w.models.iter().cloned()).collect()
+    }
+}
+
+fn lock(state: &Mutex<State>) -> MutexGuard<'_, State> {
+    state.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Answer one request: a scripted reply if there is one, else the route
+async fn answer(state: &Mutex<State>, request: Request) -> Response {
+    #[cfg(test)]
+    {
+        let scripted = {
+            let mut state = lock(state);
+            state.requests.push(request.clone());
+            let key = (request.method.clone(), request.path.clone());
+            state.scripts.get_mut(&key).and_then(|replies| match replies.len() {
+                0 => None,
+                1 => replies.front().cloned(),
+                _ => replies.pop_front(),
+            })
+        };
+        if let Some(reply) = scripted {
+            tokio::time::sleep(reply.delay).await;
+            return reply.response;
+        }
+    }
+    let mut state = lock(state);
+    route(&mut state, &request)
+}
+
+fn route(state: &mut State, request: &Request) -> Response {
+    let manage = !request.path.starts_with("/api/");
+    let token = request.header("authorization").and_then(|v| v.strip_prefix("Bearer "));
+    let authorized = match token {
+        Some(ADMIN_TOKEN) => true,
+        Some(CLIENT_TOKEN) => !manage,
+        _ => false,
+    };
+    if !authorized {
+        return error(401, "Invalid or missing token");
+    }
+    if state.live && request.path == "/queue" {
+        state.vary();
+    }
+
+    let body: Value = serde_json::from_slice(&request.body).unwrap_or(Value::Null);
+    let node = request.header("node").map(str::to_string);
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/queue") => ok(json!(state.queue)),
+        ("GET", "/worker/status") => ok(per_worker(state, |w| json!(w.statuses))),
+        ("GET", "/worker/connections") => ok(per_worker(state, |w| json!(w.connections))),
+        ("GET", "/worker/versions") => ok(per_worker(state, |w| json!({ "hive": w.hive, "ollama": w.ollama }))),
+        ("GET", "/worker/tags") => ok(per_worker(state, |w| json!(w.models))),
+        ("GET", "/worker/pings") => {
+            let now = Utc::now();
+            ok(per_worker(state, |_| {
+                let pings: Vec<String> = (0..6).rev()
+                    .map(|i| (now - chrono::Duration::seconds(i * PING_EVERY_SECS)).to_rfc3339())
+                    .collect();
+                json!(pings)
+            }))
+        }
+        ("GET", "/key") => ok(json!(state.keys)),
+        ("POST", "/key") => {
+            let (Some(name), Some(role)) = (body["name"].as_str(), body["role"].as_str()) else {
+                return error(400, "name and role are required");
+            };
+            let id = state.keys.len() + 1;
+            state.keys.push(json!({ "id": id.to_string(), "name": name, "role": role, "value": format!("mock-key-{}", id) }));
+            ok(json!(state.keys))
+        }
+        ("GET", "/api/models") => ok(json!(state.all_models())),
+        ("POST", "/api/generate") | ("POST", "/api/chat") => infer(state, request, &body),
+        ("POST", "/api/pull") => pull(state, &body, node.as_deref()),
+        ("DELETE", "/api/delete") => delete(state, &body, node.as_deref()),
+        _ => Response::not_found(),
+    }
+}
+
+fn infer(state: &State, request: &Request, body: &Value) -> Response {
+    let model = body["model"].as_str().unwrap_or_default();
+    if !state.all_models().contains(model) {
+        return error(404, &format!("model '{}' not found", model));
+    }
+    let chat = request.path == "/api/chat";
+    let prompt = if chat {
+        body["messages"].as_array().and_then(|m| m.last()).and_then(|m| m["content"].as_str())
+    } else {
+        body["prompt"].as_str()
+    };
+    let reply = format!("This is {} on the mock HiveCore, answering: {}", model, prompt.unwrap_or_default());
+    let chunk = |text: &str| if chat {
+        json!({ "model": model, "message": { "role": "assistant", "content": text }, "done": false })
+    } else {
+        json!({ "model": model, "response": text, "done": false })
+    };
+
+    let stream = body["stream"].as_bool().unwrap_or(true) || request.query.contains("stream=true");
+    if !stream {
+        let mut whole = chunk(&reply);
+        whole["done"] = json!(true);
+        return ok(whole);
+    }
+    let words: Vec<&str> = reply.split_inclusive(' ').collect();
+    let mut lines: Vec<Value> = words.iter().map(|w| chunk(w)).collect();
+    lines.push(json!({ "model": model, "done": true, "eval_count": words.len(), "eval_duration": words.len() * 20_000_000 }));
+    stream_lines(state, &lines)
+}
+
+fn pull(state: &mut State, body: &Value, node: Option<&str>) -> Response {
+    let Some(model) = body["name"].as_str().filter(|m| !m.is_empty()) else {
+        return error(400, "model name is required");
+    };
+    let Some(targets) = target_workers(state, node) else {
+        return error(404, &format!("worker '{}' not found", node.unwrap_or_default()));
+    };
+
+    let mut lines = vec![json!({ "status": "pulling manifest" })];
+    for (digest, total) in [("sha256:6a0746a1ec1a", 4_920_734_112u64), ("sha256:4fa551d4f938", 12_403)] {
+        for step in 1..=5 {
+            lines.push(json!({
+                "status": format!("pulling {}", &digest[7..]),
+                "digest": digest,
+                "total": total,
+                "completed": total * step / 5,
+            }));
+        }
+    }
+    lines.extend([
+        json!({ "status": "verifying sha256 digest" }),
+        json!({ "status": "writing manifest" }),
+        json!({ "status": "success" }),
+    ]);
+    for worker in targets {
+        if let Some(worker) = state.workers.get_mut(&worker) {
+            worker.models.insert(model.to_string());
+        }
+    }
+    stream_lines(state, &lines)
+}
+
+fn delete(state: &mut State, body: &Value, node: Option<&str>) -> Response {
+    let model = body["name"].as_str().unwrap_or_default();
+    let Some(targets) = target_workers(state, node) else {
+        return error(404, &format!("worker '{}' not found", node.unwrap_or_default()));
+    };
+    let mut removed = false;
+    for worker in targets {
+        if let Some(worker) = state.workers.get_mut(&worker) {
+            removed |= worker.models.remove(model);
+        }
+    }
+    if removed {
+        stream_lines(state, &[json!({ "status": "success" })])
+    } else {
+        error(404, &format!("model '{}' not found", model))
+    }
+}
+
+/// Workers a request applies to: the one named by its `Node` header, or all
+fn target_workers(state: &State, node: Option<&str>) -> Option<Vec<String>> {
+    match node {
+        Some(node) => state.workers.contains_key(node).then(|| vec![node.to_string()]),
+        None => Some(state.workers.keys().cloned().collect()),
+    }
+}
+
+fn per_worker(state: &State, value: impl Fn(&Worker) -> Value) -> Value {
+    Value::Object(state.workers.iter().map(|(name, w)| (name.clone(), value(w))).collect())
+}
+
+fn ok(value: Value) -> Response {
+    Response::new(200, "application/json", value.to_string())
+}
+
+fn error(status: u16, message: &str) -> Response {
+    Response::new(status, "application/json", json!({ "error": message }).to_string())
+}
+
+fn ndjson_body(lines: &[Value]) -> String {
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+fn stream_lines(state: &State, lines: &[Value]) -> Response {
+    let response = Response::new(200, "application/x-ndjson", ndjson_body(lines));
+    match state.pace {
+        Some(delay) => response.paced(delay),
+        None => response,
+    }
+}
+
+/// Wait up to five seconds for `done` to hold on the shared app state
+#[cfg(test)]
+pub async fn wait_until(
+    app_arc: &std::sync::Arc<tokio::sync::Mutex<crate::app::App>>,
+    what: &str,
+    done: impl Fn(&crate::app::App) -> bool,
+) {
+    for _ in 0..100 {
+        if done(&*app_arc.lock().await) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("timed out waiting for {}", what);
+}
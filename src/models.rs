@@ -2,6 +2,7 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
 // Versions returned by /worker/versions
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,12 +21,281 @@ pub type WorkerConnections = HashMap<String, usize>;
 // Pings returned by /worker/pings: array of RFC3339 timestamps per worker
 pub type WorkerPings = HashMap<String, Vec<DateTime<Utc>>>;
 
+// Heartbeat statistics derived from a worker's ping timestamps
+#[derive(Debug, Clone, PartialEq)]
+pub struct PingStats {
+    /// Seconds between consecutive pings, oldest first
+    pub intervals: Vec<f64>,
+    pub mean: f64,
+    pub min: f64,
+    pub max: f64,
+    /// Standard deviation of the intervals
+    pub jitter: f64,
+}
+
+impl PingStats {
+    /// `None` with fewer than two pings
+    pub fn from_pings(times: &[DateTime<Utc>]) -> Option<Self> {
+        let mut sorted = times.to_vec();
+        sorted.sort();
+        let intervals: Vec<f64> = sorted.windows(2)
+            .map(|w| (w[1] - w[0]).num_milliseconds() as f64 / 1000.0)
+            .collect();
+        if intervals.is_empty() {
+            return None;
+        }
+        let n = intervals.len() as f64;
+        let mean = intervals.iter().sum::<f64>() / n;
+        let variance = intervals.iter().map(|i| (i - mean).powi(2)).sum::<f64>() / n;
+        Some(PingStats {
+            mean,
+            min: intervals.iter().copied().fold(f64::INFINITY, f64::min),
+            max: intervals.iter().copied().fold(0.0, f64::max),
+            jitter: variance.sqrt(),
+            intervals,
+        })
+    }
+}
+
+// Share of one state within a window of a worker's status vector
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateShare {
+    pub state: String,
+    pub samples: usize,
+    /// Estimated time in the state, when pings line up with the statuses
+    pub seconds: Option<f64>,
+}
+
+// Time-in-state breakdown over the last entries of a worker's status vector
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusBreakdown {
+    /// Most frequent state first
+    pub shares: Vec<StateShare>,
+    /// Fraction of the window spent Working, 0.0 to 1.0
+    pub utilisation: f64,
+}
+
+impl StatusBreakdown {
+    /// Breakdown of the last `window` statuses. When `pings` has one
+    /// timestamp per status, each status lasts until the next ping (the
+    /// newest until `now`); otherwise only sample counts are known.
+    pub fn from_statuses(statuses: &[String], pings: Option<&[DateTime<Utc>]>, window: usize, now: DateTime<Utc>) -> Self {
+        let start = statuses.len().saturating_sub(window);
+        let durations: Option<Vec<f64>> = pings
+            .filter(|p| p.len() == statuses.len())
+            .map(|p| {
+                (start..p.len())
+                    .map(|i| {
+                        let end = p.get(i + 1).copied().unwrap_or(now);
+                        ((end - p[i]).num_milliseconds() as f64 / 1000.0).max(0.0)
+                    })
+                    .collect()
+            });
+
+        let mut shares: Vec<StateShare> = Vec::new();
+        for (offset, state) in statuses[start..].iter().enumerate() {
+            let duration = durations.as_ref().map(|d| d[offset]);
+            match shares.iter_mut().find(|s| &s.state == state) {
+                Some(share) => {
+                    share.samples += 1;
+                    share.seconds = share.seconds.zip(duration).map(|(a, b)| a + b);
+                }
+                None => shares.push(StateShare { state: state.clone(), samples: 1, seconds: duration }),
+            }
+        }
+        shares.sort_by(|a, b| b.samples.cmp(&a.samples).then_with(|| a.state.cmp(&b.state)));
+
+        let working = shares.iter().find(|s| s.state == "Working");
+        let utilisation = match (&durations, working) {
+            (_, None) => 0.0,
+            (Some(d), Some(w)) => {
+                let total: f64 = d.iter().sum();
+                if total > 0.0 { w.seconds.unwrap_or(0.0) / total } else { 0.0 }
+            }
+            (None, Some(w)) => w.samples as f64 / (statuses.len() - start) as f64,
+        };
+        StatusBreakdown { shares, utilisation }
+    }
+}
+
 // Supported tags per worker
 pub type WorkerTags = HashMap<String, Vec<String>>;
 
 // Queue map: model name or node name to count
 pub type QueueMap = HashMap<String, usize>;
 
+// Headline numbers for one cluster, derived from status, connections and queue
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClusterSummary {
+    pub workers: usize,
+    pub busy: usize,
+    pub connections: usize,
+    pub queued: usize,
+}
+
+impl ClusterSummary {
+    pub fn from_data(statuses: &WorkerStatuses, connections: &WorkerConnections, queue: &QueueMap) -> Self {
+        let workers = statuses.keys().filter(|n| *n != "Unauthenticated").count();
+        let busy = statuses.iter()
+            .filter(|(n, s)| *n != "Unauthenticated" && s.last().is_some_and(|s| s == "Working"))
+            .count();
+        let connections = connections.iter()
+            .filter(|(n, _)| *n != "Unauthenticated")
+            .map(|(_, c)| c)
+            .sum();
+        // Node queues repeat requests already counted per model
+        let queued = queue.iter()
+            .filter(|(k, _)| !k.starts_with("Node:"))
+            .map(|(_, c)| c)
+            .sum();
+        ClusterSummary { workers, busy, connections, queued }
+    }
+}
+
+// One line of a pull or delete stream; download lines carry `digest`, `total` and `completed`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PullStatus {
+    #[serde(default)]
+    pub status: String,
+    pub message: Option<String>,
+    pub digest: Option<String>,
+    pub total: Option<u64>,
+    pub completed: Option<u64>,
+}
+
+impl PullStatus {
+    /// Text to show for the line: its status, else its message
+    pub fn text(&self) -> &str {
+        match &self.message {
+            Some(message) if self.status.is_empty() => message,
+            _ => &self.status,
+        }
+    }
+}
+
+// Progress of a pull or delete, sent by `HiveInferClient` to whoever shows it
+#[derive(Debug, Clone)]
+pub enum ActionEvent {
+    /// A status line without byte counts
+    Status(String),
+    /// Byte counts of one layer
+    Progress(PullStatus),
+    /// An error or malformed line; the action carries on
+    LineError(String),
+    /// The stream ended; `success` is false if any line failed
+    Finished { success: bool },
+}
+
+/// Action events buffered between a client and the UI
+pub const ACTION_EVENT_BUFFER: usize = 64;
+
+/// Rates below this many bytes per second count as stalled, without an ETA
+const MIN_RATE: f64 = 1.0;
+
+/// Time to move `remaining` bytes at `rate`; `None` while the rate is too
+/// low to tell
+fn eta_at(remaining: u64, rate: f64) -> Option<Duration> {
+    if rate < MIN_RATE {
+        return None;
+    }
+    Duration::try_from_secs_f64(remaining as f64 / rate).ok()
+}
+
+// Download progress of one layer of a pull
+#[derive(Debug, Clone)]
+pub struct LayerProgress {
+    pub digest: String,
+    pub total: u64,
+    pub completed: u64,
+    /// Smoothed transfer rate in bytes per second
+    pub rate: f64,
+    last_update: Instant,
+    last_completed: u64,
+}
+
+impl LayerProgress {
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 { 0.0 } else { (self.completed as f64 / self.total as f64).min(1.0) }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.total > 0 && self.completed >= self.total
+    }
+
+    /// Time left at the current rate; `None` until a rate is known or
+    /// while the layer is stalled
+    pub fn eta(&self) -> Option<Duration> {
+        if self.is_done() {
+            return None;
+        }
+        eta_at(self.total.saturating_sub(self.completed), self.rate)
+    }
+}
+
+// Per-layer progress of a running pull, in the order layers first appeared
+#[derive(Debug, Clone, Default)]
+pub struct PullProgress {
+    pub layers: Vec<LayerProgress>,
+}
+
+impl PullProgress {
+    /// Rates are re-estimated at most this often
+    const RATE_WINDOW: Duration = Duration::from_millis(250);
+    /// Weight of the newest rate sample in the moving average
+    const RATE_SMOOTHING: f64 = 0.3;
+
+    /// Fold a stream line into the layer it reports on; `false` for
+    /// lines without byte counts
+    pub fn update(&mut self, line: &PullStatus, now: Instant) -> bool {
+        let (Some(digest), Some(total)) = (&line.digest, line.total) else { return false };
+        let completed = line.completed.unwrap_or(0);
+        let Some(layer) = self.layers.iter_mut().find(|l| &l.digest == digest) else {
+            self.layers.push(LayerProgress {
+                digest: digest.clone(),
+                total,
+                completed,
+                rate: 0.0,
+                last_update: now,
+                last_completed: completed,
+            });
+            return true;
+        };
+        layer.total = total;
+        layer.completed = completed;
+        let elapsed = now.duration_since(layer.last_update);
+        if elapsed >= Self::RATE_WINDOW {
+            let sample = completed.saturating_sub(layer.last_completed) as f64 / elapsed.as_secs_f64();
+            layer.rate = if layer.rate == 0.0 {
+                sample
+            } else {
+                layer.rate * (1.0 - Self::RATE_SMOOTHING) + sample * Self::RATE_SMOOTHING
+            };
+            layer.last_update = now;
+            layer.last_completed = completed;
+        }
+        true
+    }
+
+    /// (completed, total) bytes over every layer
+    pub fn totals(&self) -> (u64, u64) {
+        self.layers.iter().fold((0, 0), |(c, t), l| (c + l.completed, t + l.total))
+    }
+
+    /// Combined rate of the layers still downloading
+    pub fn rate(&self) -> f64 {
+        self.layers.iter().filter(|l| !l.is_done()).map(|l| l.rate).sum()
+    }
+
+    /// Time left for every layer at the combined rate
+    pub fn eta(&self) -> Option<Duration> {
+        let (completed, total) = self.totals();
+        if completed >= total {
+            return None;
+        }
+        eta_at(total - completed, self.rate())
+    }
+}
+
 // Authentication key
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuthKey {
@@ -36,24 +306,6 @@ pub struct AuthKey {
 }
 pub type AuthKeys = Vec<AuthKey>;
 
-// Inference request payload
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GenerateRequest {
-    pub model: String,
-    pub prompt: String,
-    pub stream: bool,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub node: Option<String>,
-}
-
-// Inference response (non-streamed)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GenerateResponse {
-    pub result: String,
-    #[serde(flatten)]
-    pub extra: HashMap<String, serde_json::Value>,
-}
-
 // Chat API
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatMessage {
@@ -81,3 +333,157 @@ pub struct EmbedResponse {
     #[serde(flatten)]
     pub extra: HashMap<String, serde_json::Value>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(digest: &str, total: u64, completed: u64) -> PullStatus {
+        PullStatus {
+            status: "pulling".into(),
+            digest: Some(digest.into()),
+            total: Some(total),
+            completed: Some(completed),
+            ..PullStatus::default()
+        }
+    }
+
+    #[test]
+    fn update_tracks_layers_in_order_of_appearance() {
+        let start = Instant::now();
+        let mut progress = PullProgress::default();
+        assert!(!progress.update(&PullStatus { status: "pulling manifest".into(), ..PullStatus::default() }, start));
+        assert!(progress.update(&line("sha256:a", 1000, 0), start));
+        assert!(progress.update(&line("sha256:b", 500, 100), start));
+        assert!(progress.update(&line("sha256:a", 1000, 400), start));
+
+        let digests: Vec<&str> = progress.layers.iter().map(|l| l.digest.as_str()).collect();
+        assert_eq!(digests, ["sha256:a", "sha256:b"]);
+        assert_eq!(progress.totals(), (500, 1500));
+    }
+
+    #[test]
+    fn rate_is_smoothed_and_only_sampled_per_window() {
+        let start = Instant::now();
+        let mut progress = PullProgress::default();
+        progress.update(&line("sha256:a", 10_000, 0), start);
+        // Too soon after the first line to estimate a rate
+        progress.update(&line("sha256:a", 10_000, 50), start + Duration::from_millis(100));
+        assert_eq!(progress.rate(), 0.0);
+
+        progress.update(&line("sha256:a", 10_000, 1000), start + Duration::from_secs(1));
+        assert_eq!(progress.rate(), 1000.0);
+        progress.update(&line("sha256:a", 10_000, 4000), start + Duration::from_secs(2));
+        assert!((progress.rate() - (1000.0 * 0.7 + 3000.0 * 0.3)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn finished_layers_leave_the_combined_rate() {
+        let start = Instant::now();
+        let mut progress = PullProgress::default();
+        for (done, at) in [(0, 0), (100, 1)] {
+            progress.update(&line("sha256:a", 100, done), start + Duration::from_secs(at));
+            progress.update(&line("sha256:b", 1000, done), start + Duration::from_secs(at));
+        }
+        assert!(progress.layers[0].is_done());
+        assert_eq!(progress.rate(), 100.0);
+        assert_eq!(progress.layers[0].eta(), None);
+        assert_eq!(progress.layers[1].eta(), Some(Duration::from_secs(9)));
+        assert_eq!(progress.eta(), Some(Duration::from_secs(9)));
+    }
+
+    #[test]
+    fn a_stalled_pull_has_no_eta() {
+        let start = Instant::now();
+        let mut progress = PullProgress::default();
+        progress.update(&line("sha256:a", u64::MAX, 0), start);
+        progress.update(&line("sha256:a", u64::MAX, 1000), start + Duration::from_secs(1));
+        // Zero-byte samples shrink the rate towards, but never to, zero
+        for i in 0..400u64 {
+            progress.update(&line("sha256:a", u64::MAX, 1000), start + Duration::from_millis(1250 + 250 * i));
+        }
+        assert!(progress.rate() > 0.0);
+        assert_eq!(progress.layers[0].eta(), None);
+        assert_eq!(progress.eta(), None);
+    }
+
+    #[test]
+    fn eta_is_none_when_it_does_not_fit_a_duration() {
+        assert_eq!(eta_at(u64::MAX, 1.0), None);
+        assert_eq!(eta_at(10, 0.5), None);
+        assert_eq!(eta_at(10, 2.0), Some(Duration::from_secs(5)));
+    }
+
+    fn at(seconds: f64) -> DateTime<Utc> {
+        DateTime::UNIX_EPOCH + chrono::TimeDelta::milliseconds((seconds * 1000.0) as i64)
+    }
+
+    #[test]
+    fn ping_stats_need_two_pings() {
+        assert_eq!(PingStats::from_pings(&[]), None);
+        assert_eq!(PingStats::from_pings(&[at(5.0)]), None);
+    }
+
+    #[test]
+    fn ping_stats_measure_unordered_pings() {
+        let stats = PingStats::from_pings(&[at(10.0), at(0.0), at(14.0), at(4.0)]).unwrap();
+        assert_eq!(stats.intervals, [4.0, 6.0, 4.0]);
+        assert!((stats.mean - 14.0 / 3.0).abs() < 1e-9);
+        assert_eq!((stats.min, stats.max), (4.0, 6.0));
+        // Deviations of 2/3, 4/3 and 2/3 from the mean
+        assert!((stats.jitter - (8.0f64 / 9.0).sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn even_pings_have_no_jitter() {
+        let stats = PingStats::from_pings(&[at(0.0), at(2.5), at(5.0)]).unwrap();
+        assert_eq!((stats.mean, stats.min, stats.max, stats.jitter), (2.5, 2.5, 2.5, 0.0));
+    }
+
+    fn states(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    /// (state, samples, seconds) of each share
+    fn shares(breakdown: &StatusBreakdown) -> Vec<(&str, usize, Option<f64>)> {
+        breakdown.shares.iter().map(|s| (s.state.as_str(), s.samples, s.seconds)).collect()
+    }
+
+    #[test]
+    fn breakdown_counts_samples_without_matching_pings() {
+        let statuses = states(&["Polling", "Working", "Working", "Polling", "Working"]);
+        let pings = [at(0.0), at(10.0)];
+        let breakdown = StatusBreakdown::from_statuses(&statuses, Some(&pings), 10, at(20.0));
+        assert_eq!(shares(&breakdown), [("Working", 3, None), ("Polling", 2, None)]);
+        assert!((breakdown.utilisation - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn breakdown_times_each_status_until_the_next_ping() {
+        let statuses = states(&["Polling", "Working", "Polling"]);
+        let pings = [at(0.0), at(10.0), at(40.0)];
+        let breakdown = StatusBreakdown::from_statuses(&statuses, Some(&pings), 10, at(50.0));
+        assert_eq!(shares(&breakdown), [("Polling", 2, Some(20.0)), ("Working", 1, Some(30.0))]);
+        assert!((breakdown.utilisation - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn breakdown_only_covers_the_window() {
+        let statuses = states(&["Working", "Working", "Polling", "Idle"]);
+        let pings = [at(0.0), at(10.0), at(20.0), at(25.0)];
+        let breakdown = StatusBreakdown::from_statuses(&statuses, Some(&pings), 3, at(30.0));
+        // Equal counts sort by name
+        assert_eq!(shares(&breakdown), [("Idle", 1, Some(5.0)), ("Polling", 1, Some(5.0)), ("Working", 1, Some(10.0))]);
+        assert!((breakdown.utilisation - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn breakdown_without_working_or_elapsed_time_is_idle() {
+        let breakdown = StatusBreakdown::from_statuses(&states(&["Polling"]), None, 10, at(0.0));
+        assert_eq!(breakdown.utilisation, 0.0);
+        let breakdown = StatusBreakdown::from_statuses(&states(&["Working"]), Some(&[at(5.0)]), 10, at(5.0));
+        assert_eq!(breakdown.utilisation, 0.0);
+        let breakdown = StatusBreakdown::from_statuses(&[], None, 10, at(0.0));
+        assert!(breakdown.shares.is_empty());
+    }
+}
@@ -0,0 +1,387 @@
+// src/notify.rs
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use std::{collections::VecDeque, fs, sync::Arc, time::{Duration, Instant}};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use crate::config::config_path;
+use crate::errors::ClientError;
+
+/// Kinds of events that can be routed to sinks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    AlertFired,
+    AlertResolved,
+    /// A model pull or delete finished with errors
+    ActionFailed,
+}
+
+impl EventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventKind::AlertFired => "alert_fired",
+            EventKind::AlertResolved => "alert_resolved",
+            EventKind::ActionFailed => "action_failed",
+        }
+    }
+}
+
+/// Payload posted to webhooks and exported to commands
+#[derive(Debug, Clone, Serialize)]
+pub struct NotifyEvent {
+    pub kind: EventKind,
+    /// Profile the event happened on
+    pub profile: String,
+    /// Worker, model or alert id the event is about
+    pub subject: String,
+    pub message: String,
+    pub at: DateTime<Utc>,
+}
+
+/// A delivery target from the `[[sinks]]` tables of alerts.toml
+///
+/// ```toml
+/// [[sinks]]
+/// kind = "webhook"
+/// url = "https://chat.example.com/hooks/hive"
+/// events = ["alert_fired", "action_failed"]
+///
+/// [[sinks]]
+/// kind = "command"
+/// command = "notify-send \"$HIVE_SUBJECT\" \"$HIVE_MESSAGE\""
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SinkConfig {
+    #[serde(flatten)]
+    pub target: Target,
+    /// Event kinds delivered to this sink; empty means all
+    #[serde(default)]
+    pub events: Vec<EventKind>,
+    /// Extra attempts after a failed delivery
+    #[serde(default = "default_retries")]
+    pub retries: u32,
+    /// Delay before the first retry, doubled for each further one
+    #[serde(default = "default_backoff_ms")]
+    pub backoff_ms: u64,
+    /// Events delivered per minute at most; the rest are dropped
+    #[serde(default = "default_max_per_minute")]
+    pub max_per_minute: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Target {
+    /// POST the event as JSON
+    Webhook { url: String },
+    /// Run through `sh -c` with the event in `HIVE_*` environment variables
+    Command { command: String },
+}
+
+fn default_retries() -> u32 { 3 }
+fn default_backoff_ms() -> u64 { 1000 }
+fn default_max_per_minute() -> usize { 20 }
+
+/// Wrapper for the sink part of alerts.toml
+#[derive(Debug, Default, Deserialize)]
+struct NotifyFile {
+    #[serde(default)]
+    sinks: Vec<SinkConfig>,
+}
+
+/// Load notification sinks from alerts.toml next to profiles.toml
+pub fn load_sinks() -> Result<Vec<SinkConfig>, ClientError> {
+    let path = config_path("alerts.toml")?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let contents = fs::read_to_string(path)?;
+    let file: NotifyFile = toml::from_str(&contents)?;
+    Ok(file.sinks)
+}
+
+/// One configured sink with its rate limit state
+pub struct Sink {
+    pub config: SinkConfig,
+    client: reqwest::Client,
+    /// Delivery times within the last minute
+    sent: VecDeque<Instant>,
+}
+
+impl Sink {
+    pub fn new(config: SinkConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+        Sink { config, client, sent: VecDeque::new() }
+    }
+
+    /// Short description for error messages
+    pub fn label(&self) -> &str {
+        match &self.config.target {
+            Target::Webhook { url } => url,
+            Target::Command { command } => command,
+        }
+    }
+
+    /// Take a slot in the per-minute budget, if one is left
+    pub fn allow(&mut self, now: Instant) -> bool {
+        while self.sent.front().is_some_and(|t| now.duration_since(*t) >= Duration::from_secs(60)) {
+            self.sent.pop_front();
+        }
+        if self.sent.len() >= self.config.max_per_minute {
+            return false;
+        }
+        self.sent.push_back(now);
+        true
+    }
+
+    /// Deliver once
+    pub async fn deliver(&self, event: &NotifyEvent) -> Result<(), String> {
+        match &self.config.target {
+            Target::Webhook { url } => {
+                self.client.post(url)
+                    .json(event)
+                    .send()
+                    .await
+                    .and_then(|r| r.error_for_status())
+                    .map(|_| ())
+                    .map_err(|e| e.to_string())
+            }
+            Target::Command { command } => {
+                let status = tokio::process::Command::new("sh")
+                    .arg("-c")
+                    .arg(command)
+                    .env("HIVE_EVENT", event.kind.as_str())
+                    .env("HIVE_PROFILE", &event.profile)
+                    .env("HIVE_SUBJECT", &event.subject)
+                    .env("HIVE_MESSAGE", &event.message)
+                    .env("HIVE_TIME", event.at.to_rfc3339())
+                    .stdin(std::process::Stdio::null())
+                    .stdout(std::process::Stdio::null())
+                    .stderr(std::process::Stdio::null())
+                    .status()
+                    .await
+                    .map_err(|e| e.to_string())?;
+                if status.success() {
+                    Ok(())
+                } else {
+                    Err(format!("exited with {}", status))
+                }
+            }
+        }
+    }
+
+    /// Deliver, retrying with exponential backoff
+    pub async fn deliver_with_retries(&self, event: &NotifyEvent) -> Result<(), String> {
+        let mut delay = Duration::from_millis(self.config.backoff_ms);
+        let mut attempt = 0;
+        loop {
+            match self.deliver(event).await {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt >= self.config.retries => return Err(e),
+                Err(_) => {
+                    attempt += 1;
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
+                }
+            }
+        }
+    }
+}
+
+/// Handle for queueing events; each sink delivers from its own task so a
+/// slow endpoint does not hold up the others
+#[derive(Debug, Clone, Default)]
+pub struct Notifier {
+    sinks: Vec<(Vec<EventKind>, UnboundedSender<NotifyEvent>)>,
+}
+
+impl Notifier {
+    /// Start one delivery task per sink. `report` is called with a message
+    /// whenever an event is dropped or fails after all retries.
+    pub fn spawn(configs: Vec<SinkConfig>, report: impl Fn(String) + Send + Sync + 'static) -> Self {
+        let report = Arc::new(report);
+        let sinks = configs.into_iter().map(|config| {
+            let events = config.events.clone();
+            let (tx, mut rx) = unbounded_channel::<NotifyEvent>();
+            let report = report.clone();
+            tokio::spawn(async move {
+                let mut sink = Sink::new(config);
+                let mut limited = false;
+                while let Some(event) = rx.recv().await {
+                    if !sink.allow(Instant::now()) {
+                        // Report once per burst rather than once per dropped event
+                        if !limited {
+                            report(format!("Notification rate limit reached for {}, dropping events.", sink.label()));
+                        }
+                        limited = true;
+                        continue;
+                    }
+                    limited = false;
+                    if let Err(e) = sink.deliver_with_retries(&event).await {
+                        report(format!("Notification to {} failed: {}", sink.label(), e));
+                    }
+                }
+            });
+            (events, tx)
+        }).collect();
+        Notifier { sinks }
+    }
+
+    /// Queue an event for every sink routed its kind
+    pub fn send(&self, event: NotifyEvent) {
+        for (events, tx) in &self.sinks {
+            if routed(events, event.kind) {
+                let _ = tx.send(event.clone());
+            }
+        }
+    }
+}
+
+/// Whether a sink with the `events` filter receives events of `kind`
+fn routed(events: &[EventKind], kind: EventKind) -> bool {
+    events.is_empty() || events.contains(&kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn event(kind: EventKind) -> NotifyEvent {
+        NotifyEvent {
+            kind,
+            profile: "local".into(),
+            subject: "gpu-1".into(),
+            message: "gpu-1 has not pinged in 90s".into(),
+            at: Utc::now(),
+        }
+    }
+
+    fn sink(target: Target) -> Sink {
+        Sink::new(SinkConfig {
+            target,
+            events: Vec::new(),
+            retries: 2,
+            backoff_ms: 10,
+            max_per_minute: 20,
+        })
+    }
+
+    /// Serve one response per status in `statuses`, returning the request bodies
+    async fn stand_in(statuses: Vec<u16>) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let mut bodies = Vec::new();
+            for status in statuses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0u8; 4096];
+                // Read headers, then as much body as Content-Length announces
+                loop {
+                    let n = socket.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&request);
+                    if let Some(end) = text.find("\r\n\r\n") {
+                        let length = text.lines()
+                            .find_map(|l| l.to_ascii_lowercase().strip_prefix("content-length:").map(|v| v.trim().parse::<usize>().unwrap()))
+                            .unwrap_or(0);
+                        if request.len() >= end + 4 + length {
+                            bodies.push(text[end + 4..].to_string());
+                            break;
+                        }
+                    }
+                    if n == 0 {
+                        break;
+                    }
+                }
+                let response = format!("HTTP/1.1 {} X\r\ncontent-length: 0\r\nconnection: close\r\n\r\n", status);
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+            bodies
+        });
+        (url, handle)
+    }
+
+    #[tokio::test]
+    async fn webhook_posts_event_json() {
+        let (url, server) = stand_in(vec![200]).await;
+        let sink = sink(Target::Webhook { url });
+        sink.deliver(&event(EventKind::AlertFired)).await.unwrap();
+
+        let bodies = server.await.unwrap();
+        let body: serde_json::Value = serde_json::from_str(&bodies[0]).unwrap();
+        assert_eq!(body["kind"], "alert_fired");
+        assert_eq!(body["profile"], "local");
+        assert_eq!(body["subject"], "gpu-1");
+    }
+
+    #[tokio::test]
+    async fn webhook_retries_server_errors() {
+        let (url, server) = stand_in(vec![500, 503, 200]).await;
+        let sink = sink(Target::Webhook { url });
+        sink.deliver_with_retries(&event(EventKind::AlertFired)).await.unwrap();
+        assert_eq!(server.await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn webhook_gives_up_after_retries() {
+        let (url, server) = stand_in(vec![500, 500, 500]).await;
+        let sink = sink(Target::Webhook { url });
+        assert!(sink.deliver_with_retries(&event(EventKind::AlertFired)).await.is_err());
+        assert_eq!(server.await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn command_gets_event_environment() {
+        let ok = sink(Target::Command {
+            command: "test \"$HIVE_EVENT\" = action_failed && test \"$HIVE_SUBJECT\" = gpu-1".into(),
+        });
+        assert!(ok.deliver(&event(EventKind::ActionFailed)).await.is_ok());
+        assert!(ok.deliver(&event(EventKind::AlertFired)).await.is_err());
+    }
+
+    #[test]
+    fn routes_by_event_kind() {
+        assert!(routed(&[], EventKind::AlertResolved));
+        assert!(routed(&[EventKind::AlertFired], EventKind::AlertFired));
+        assert!(!routed(&[EventKind::AlertFired], EventKind::AlertResolved));
+    }
+
+    #[test]
+    fn rate_limit_is_per_minute() {
+        let mut limited = sink(Target::Command { command: "true".into() });
+        limited.config.max_per_minute = 2;
+        let start = Instant::now();
+        assert!(limited.allow(start));
+        assert!(limited.allow(start));
+        assert!(!limited.allow(start + Duration::from_secs(30)));
+        assert!(limited.allow(start + Duration::from_secs(61)));
+    }
+
+    #[test]
+    fn parses_sinks_table() {
+        let file: NotifyFile = toml::from_str(r#"
+            [[rules]]
+            kind = "version_mismatch"
+
+            [[sinks]]
+            kind = "webhook"
+            url = "http://localhost:9000/hook"
+            events = ["alert_fired"]
+
+            [[sinks]]
+            kind = "command"
+            command = "logger hive"
+            retries = 0
+        "#).unwrap();
+        assert_eq!(file.sinks.len(), 2);
+        assert!(matches!(file.sinks[0].target, Target::Webhook { .. }));
+        assert_eq!(file.sinks[0].events, vec![EventKind::AlertFired]);
+        assert_eq!(file.sinks[1].retries, 0);
+        assert_eq!(file.sinks[1].max_per_minute, default_max_per_minute());
+    }
+}
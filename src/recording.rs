@@ -0,0 +1,335 @@
+// src/recording.rs
+//! Session recording and offline replay.
+//!
+//! With `--record` every polled snapshot is appended to a JSONL file, one
+//! `{"at", "profile", "resource", "data"}` object per line. `--replay`
+//! reads such a file back and feeds the snapshots to `App::apply_poll_at`
+//! on a clock that can be paused, sped up and moved.
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::app::{PollData, Resource};
+use crate::errors::ClientError;
+
+/// Playback speeds, slowest first
+pub const SPEEDS: [f64; 7] = [0.25, 0.5, 1.0, 2.0, 4.0, 8.0, 16.0];
+/// Index of real time in `SPEEDS`
+const REAL_TIME: usize = 2;
+
+/// One polled payload and when it arrived
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub at: DateTime<Utc>,
+    /// Name of the profile it was polled from
+    pub profile: String,
+    #[serde(flatten)]
+    pub data: PollData,
+}
+
+/// Appends snapshots to a recording file
+#[derive(Debug)]
+pub struct Recorder {
+    out: BufWriter<File>,
+}
+
+impl Recorder {
+    /// Open `path` for appending, creating it if needed
+    pub fn create(path: &Path) -> Result<Self, ClientError> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Recorder { out: BufWriter::new(file) })
+    }
+
+    /// Write one snapshot as a line. API keys hold secrets and are skipped.
+    pub fn record(&mut self, at: DateTime<Utc>, profile: &str, data: &PollData) -> Result<(), ClientError> {
+        if data.resource() == Resource::Keys {
+            return Ok(());
+        }
+        let snapshot = Snapshot { at, profile: profile.to_string(), data: data.clone() };
+        serde_json::to_writer(&mut self.out, &snapshot)?;
+        writeln!(self.out)?;
+        // Flushed per line so a crash or kill loses at most one snapshot
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+/// The snapshots of one recorded profile and a playback clock over them.
+///
+/// The clock starts at the first snapshot, playing at real time. Every
+/// snapshot at or before the clock is handed out once by `due`; moving the
+/// clock back starts handing them out from the beginning again.
+#[derive(Debug)]
+pub struct Replay {
+    pub path: PathBuf,
+    /// Profile whose snapshots are played
+    pub profile: String,
+    /// Snapshots of other profiles in the file, left out
+    pub skipped: usize,
+    /// Sorted by time
+    snapshots: Vec<Snapshot>,
+    /// Snapshots before this index have been handed out
+    next: usize,
+    /// Current time of the playback clock
+    pub position: DateTime<Utc>,
+    pub playing: bool,
+    /// Index into `SPEEDS`
+    speed: usize,
+}
+
+impl Replay {
+    /// Read a recording. A file with several profiles replays the first one.
+    pub fn load(path: &Path) -> Result<Self, ClientError> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| ClientError::Recording(format!("{}: {}", path.display(), e)))?;
+        let mut snapshots = Vec::new();
+        for (number, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let snapshot: Snapshot = serde_json::from_str(line)
+                .map_err(|e| ClientError::Recording(format!("{} line {}: {}", path.display(), number + 1, e)))?;
+            snapshots.push(snapshot);
+        }
+        let Some(first) = snapshots.first() else {
+            return Err(ClientError::Recording(format!("{} holds no snapshots", path.display())));
+        };
+        let profile = first.profile.clone();
+        let total = snapshots.len();
+        snapshots.retain(|s| s.profile == profile);
+        snapshots.sort_by_key(|s| s.at);
+        Ok(Replay {
+            path: path.to_path_buf(),
+            profile,
+            skipped: total - snapshots.len(),
+            position: snapshots[0].at,
+            snapshots,
+            next: 0,
+            playing: true,
+            speed: REAL_TIME,
+        })
+    }
+
+    pub fn start(&self) -> DateTime<Utc> {
+        self.snapshots[0].at
+    }
+
+    pub fn end(&self) -> DateTime<Utc> {
+        self.snapshots[self.snapshots.len() - 1].at
+    }
+
+    /// Playback speed as a multiple of real time
+    pub fn speed(&self) -> f64 {
+        SPEEDS[self.speed]
+    }
+
+    /// Double or halve the playback speed within `SPEEDS`
+    pub fn change_speed(&mut self, faster: bool) {
+        self.speed = if faster {
+            (self.speed + 1).min(SPEEDS.len() - 1)
+        } else {
+            self.speed.saturating_sub(1)
+        };
+    }
+
+    /// Pause, or play on. Playing at the end starts over, and like `seek`
+    /// returns true then.
+    pub fn toggle(&mut self) -> bool {
+        self.playing = !self.playing;
+        self.playing && self.position >= self.end() && self.seek(self.start() - self.position)
+    }
+
+    /// Run the clock for `elapsed` wall time while playing, up to the end
+    pub fn advance(&mut self, elapsed: Duration) {
+        if !self.playing {
+            return;
+        }
+        let step = TimeDelta::from_std(elapsed.mul_f64(self.speed())).unwrap_or(TimeDelta::MAX);
+        self.position = self.position.checked_add_signed(step).unwrap_or(self.end()).min(self.end());
+        if self.position >= self.end() {
+            self.playing = false;
+        }
+    }
+
+    /// Move the clock by `by` within the recording. Returns true when it
+    /// moved back, so state built from later snapshots has to be dropped.
+    pub fn seek(&mut self, by: TimeDelta) -> bool {
+        let target = self.position.checked_add_signed(by).unwrap_or(self.position).clamp(self.start(), self.end());
+        let back = target < self.position;
+        if back {
+            self.next = 0;
+        }
+        self.position = target;
+        back
+    }
+
+    /// Snapshots up to the clock that were not handed out yet
+    pub fn due(&mut self) -> Vec<Snapshot> {
+        let end = self.snapshots[self.next..]
+            .iter()
+            .position(|s| s.at > self.position)
+            .map_or(self.snapshots.len(), |i| self.next + i);
+        let due = self.snapshots[self.next..end].to_vec();
+        self.next = end;
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn at(seconds: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_772_359_200 + seconds, 0).unwrap()
+    }
+
+    fn queue(len: usize) -> PollData {
+        PollData::Queue(HashMap::from([("Model: llama3.1:8b".to_string(), len)]))
+    }
+
+    fn temp_file(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("hive-recording-{}-{}.jsonl", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn queued(snapshots: &[Snapshot]) -> Vec<usize> {
+        snapshots.iter()
+            .map(|s| match &s.data {
+                PollData::Queue(q) => q["Model: llama3.1:8b"],
+                other => panic!("unexpected {:?}", other.resource()),
+            })
+            .collect()
+    }
+
+    /// A recording of lab's queue growing by one every 10 seconds
+    fn recorded(name: &str) -> PathBuf {
+        let path = temp_file(name);
+        let mut recorder = Recorder::create(&path).unwrap();
+        for i in 0..4 {
+            recorder.record(at(i * 10), "lab", &queue(i as usize)).unwrap();
+        }
+        path
+    }
+
+    #[test]
+    fn records_one_line_per_snapshot_without_keys() {
+        let path = temp_file("lines");
+        let mut recorder = Recorder::create(&path).unwrap();
+        recorder.record(at(0), "lab", &queue(3)).unwrap();
+        recorder.record(at(1), "lab", &PollData::Keys(Vec::new())).unwrap();
+        recorder.record(at(2), "lab", &PollData::Tags(HashMap::from([("gpu-01".to_string(), vec!["phi3:mini".to_string()])]))).unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        let lines: Vec<serde_json::Value> = contents.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["profile"], "lab");
+        assert_eq!(lines[0]["resource"], "queue");
+        assert_eq!(lines[0]["data"]["Model: llama3.1:8b"], 3);
+        assert_eq!(lines[1]["resource"], "tags");
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn plays_back_at_the_chosen_speed() {
+        let path = recorded("speed");
+        let mut replay = Replay::load(&path).unwrap();
+        assert_eq!(queued(&replay.due()), [0]);
+
+        replay.advance(Duration::from_secs(10));
+        assert_eq!(queued(&replay.due()), [1]);
+        replay.change_speed(true);
+        replay.advance(Duration::from_secs(10));
+        assert_eq!(queued(&replay.due()), [2, 3]);
+        assert!(!replay.playing, "stops at the end");
+        assert_eq!(replay.position, replay.end());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn pausing_stops_the_clock() {
+        let path = recorded("pause");
+        let mut replay = Replay::load(&path).unwrap();
+        replay.due();
+        replay.toggle();
+        replay.advance(Duration::from_secs(60));
+        assert!(replay.due().is_empty());
+        assert_eq!(replay.position, replay.start());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn seeking_back_hands_out_snapshots_again() {
+        let path = recorded("seek");
+        let mut replay = Replay::load(&path).unwrap();
+        assert!(!replay.seek(TimeDelta::seconds(25)));
+        assert_eq!(queued(&replay.due()), [0, 1, 2]);
+
+        assert!(replay.seek(TimeDelta::seconds(-10)));
+        assert_eq!(queued(&replay.due()), [0, 1]);
+        assert!(replay.seek(TimeDelta::seconds(-600)));
+        assert_eq!(replay.position, replay.start());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn replays_the_first_profile_of_a_mixed_file() {
+        let path = recorded("mixed");
+        let mut recorder = Recorder::create(&path).unwrap();
+        recorder.record(at(5), "staging", &queue(9)).unwrap();
+
+        let mut replay = Replay::load(&path).unwrap();
+        assert_eq!(replay.profile, "lab");
+        assert_eq!(replay.skipped, 1);
+        replay.seek(TimeDelta::seconds(60));
+        assert_eq!(queued(&replay.due()), [0, 1, 2, 3]);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn an_app_replays_what_another_recorded() {
+        use crate::app::App;
+        use crate::config::Profile;
+
+        let path = temp_file("app");
+        let profile = Profile::single_port("lab", "http://hive.lab", 6668);
+        let mut live = App::new(vec![profile.clone()]);
+        live.set_active_profile(0);
+        live.recorder = Some(Recorder::create(&path).unwrap());
+        for (i, len) in [4, 7].into_iter().enumerate() {
+            live.apply_poll_at(queue(len), at(i as i64 * 10));
+        }
+
+        let mut app = App::new(vec![profile]);
+        app.set_active_profile(0);
+        app.replay = Some(Replay::load(&path).unwrap());
+        app.step_replay(Duration::ZERO);
+        assert_eq!(app.queue_map.as_ref().unwrap()["Model: llama3.1:8b"], 4);
+        app.seek_replay(TimeDelta::seconds(10));
+        assert_eq!(app.queue_map.as_ref().unwrap()["Model: llama3.1:8b"], 7);
+        assert_eq!(app.history.total_queued.len(), 2);
+
+        app.seek_replay(TimeDelta::seconds(-5));
+        assert_eq!(app.queue_map.as_ref().unwrap()["Model: llama3.1:8b"], 4);
+        assert_eq!(app.history.total_queued.len(), 1, "history is rebuilt up to the new position");
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn rejects_malformed_and_empty_files() {
+        let path = temp_file("malformed");
+        fs::write(&path, "{\"at\":\"2026-03-01T10:00:00Z\",\"profile\":\"lab\",\"resource\":\"queue\",\"data\":{}}\nnot json\n").unwrap();
+        let err = Replay::load(&path).unwrap_err().to_string();
+        assert!(err.contains("line 2"), "{}", err);
+
+        fs::write(&path, "\n").unwrap();
+        assert!(Replay::load(&path).unwrap_err().to_string().contains("no snapshots"));
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -1,3 +1,47 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::Frame;
+
+use crate::app::{App, Tab};
+
 pub mod terminal;
 pub mod events;
-pub mod tabs;
\ No newline at end of file
+pub mod tabs;
+pub mod profiles;
+pub mod worker_detail;
+
+#[cfg(test)]
+mod snapshots;
+
+/// Draw one frame: the tab bar, the current tab, the profiles overlay and banners
+pub fn draw(f: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .split(f.area());
+    terminal::draw_tab_bar(f, chunks[0], app);
+    match app.current_tab {
+        Tab::Dashboard => tabs::dashboard::draw(f, chunks[1], app),
+        Tab::Nodes => tabs::nodes::draw(f, chunks[1], app),
+        Tab::Queues => tabs::queues::draw(f, chunks[1], app),
+        Tab::Keys => tabs::keys::draw(f, chunks[1], app),
+        Tab::Logs => tabs::logs::draw(f, chunks[1], app),
+        Tab::Console => tabs::console::draw(f, chunks[1], app),
+        Tab::Fleet => tabs::fleet::draw(f, chunks[1], app),
+        Tab::Alerts => tabs::alerts::draw(f, chunks[1], app),
+        Tab::Models => tabs::models::draw(f, chunks[1], app),
+    }
+    profiles::draw(f, chunks[1], app);
+    terminal::draw_banners(f, &app.banners);
+}
+
+/// A rect of `percent_x` of the width and `height` rows centered in `area`
+pub fn centered_rect(area: Rect, percent_x: u16, height: u16) -> Rect {
+    let width = (area.width as u32 * percent_x.min(100) as u32 / 100) as u16;
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
@@ -0,0 +1,102 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+use crate::app::{App, ProfileEditor, ProfilesView, PROFILE_FIELDS};
+use crate::ui::centered_rect;
+
+const COLOR_BORDER: Color = Color::Cyan;
+const COLOR_HINT: Color = Color::DarkGray;
+
+/// Draw the profiles overlay on top of the current tab
+pub fn draw(f: &mut Frame, area: Rect, app: &App) {
+    let Some(view) = &app.profiles_view else { return };
+
+    let popup = centered_rect(area, 70, (area.height as u32 * 70 / 100) as u16);
+    f.render_widget(Clear, popup);
+
+    match &view.editor {
+        Some(editor) => draw_editor(f, popup, editor),
+        None => draw_list(f, popup, app, view),
+    }
+}
+
+fn draw_list(f: &mut Frame, area: Rect, app: &App, view: &ProfilesView) {
+    let block = Block::default()
+        .title(Span::styled("Profiles", Style::default().fg(COLOR_BORDER)))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(COLOR_BORDER));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let parts = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    let items: Vec<ListItem> = app.profiles.iter().enumerate().map(|(i, p)| {
+        let marker = if i == app.active_profile { "● " } else { "  " };
+        ListItem::new(Line::from(vec![
+            Span::styled(marker, Style::default().fg(Color::Green)),
+            Span::styled(p.name.clone(), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(format!("  {} (infer {}, manage {})", p.host, p.port_infer, p.port_manage)),
+        ]))
+    }).collect();
+    let list = List::new(items)
+        .highlight_style(Style::default().fg(Color::Black).bg(Color::LightCyan));
+    let mut state = ListState::default().with_selected(Some(view.selected));
+    f.render_stateful_widget(list, parts[0], &mut state);
+
+    let hint = if view.confirm_delete {
+        Span::styled(
+            format!("Delete profile '{}'? y/n", app.profiles.get(view.selected).map(|p| p.name.as_str()).unwrap_or("")),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )
+    } else {
+        Span::styled(
+            "Enter activate | a add | e edit | c duplicate | d delete | ESC close",
+            Style::default().fg(COLOR_HINT),
+        )
+    };
+    f.render_widget(Paragraph::new(Line::from(hint)), parts[1]);
+}
+
+fn draw_editor(f: &mut Frame, area: Rect, editor: &ProfileEditor) {
+    let title = if editor.index.is_some() { "Edit profile" } else { "New profile" };
+    let block = Block::default()
+        .title(Span::styled(title, Style::default().fg(COLOR_BORDER)))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(COLOR_BORDER));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let mut lines: Vec<Line> = Vec::new();
+    for (i, label) in PROFILE_FIELDS.iter().enumerate() {
+        let focused = i == editor.field;
+        let value = &editor.fields[i];
+        // Tokens stay hidden unless they are being edited
+        let shown = if i >= 4 && !focused {
+            "•".repeat(value.chars().count().min(24))
+        } else {
+            value.clone()
+        };
+        let style = if focused {
+            Style::default().fg(Color::Black).bg(Color::LightCyan)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:<16}", label), Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(format!(" {}{}", shown, if focused { "_" } else { "" }), style),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "TAB/UP/DOWN move | Enter save | ESC cancel",
+        Style::default().fg(COLOR_HINT),
+    )));
+    f.render_widget(Paragraph::new(lines), inner);
+}
@@ -0,0 +1,433 @@
+// src/ui/snapshots.rs
+//! Rendering snapshots of every tab and action panel state.
+//!
+//! Each case is drawn into a `TestBackend` at several sizes and compared,
+//! as plain text, with `src/ui/snapshots/<case>_<width>x<height>.txt`.
+//! Fixture pings are relative to the time of the test, so durations come
+//! out the same on every run; absolute timestamps and local clock times,
+//! which depend on the machine's time zone, are masked with `#`.
+//! A case also fails, and is not written, if the tab bar leaves out the
+//! active tab.
+//! Run `UPDATE_SNAPSHOTS=1 cargo test ui::snapshots` to write new or
+//! changed snapshots, then review them in the diff.
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::Terminal;
+use serde_json::json;
+
+use crate::app::{
+    ActionPanelState, ActionTarget, ActionType, App, BatchRow, BatchState, ClusterState, EndpointHealth, Focus,
+    ModelPicker, PickerStage, Resource, Tab,
+};
+use crate::app::PollData;
+use crate::config::Profile;
+use crate::models::{ClusterSummary, PullStatus};
+use crate::recording::{Recorder, Replay};
+
+/// Terminal sizes every case is rendered at: the smallest the layout is
+/// meant for, a common default and a large one
+const SIZES: [(u16, u16); 3] = [(80, 24), (120, 36), (200, 50)];
+
+/// `seconds` before now. A quarter second on top keeps durations derived
+/// from it from rounding differently when drawing takes a few milliseconds.
+fn ago(seconds: i64) -> String {
+    (Utc::now() - chrono::Duration::milliseconds(seconds * 1000 + 250)).to_rfc3339()
+}
+
+/// An app connected to a three-worker cluster with all data loaded
+fn fixture() -> App {
+    let mut app = App::new(vec![Profile::single_port("lab", "http://hive.lab", 6668), Profile::single_port("staging", "http://hive.staging", 6668)]);
+    app.set_active_profile(0);
+
+    app.worker_statuses = Some(serde_json::from_value(json!({
+        "gpu-01": ["Polling", "Working"],
+        "gpu-02": ["Polling"],
+        "cpu-01": ["Working", "Polling"],
+        "Unauthenticated": [],
+    })).unwrap());
+    app.worker_connections = Some(serde_json::from_value(json!({
+        "gpu-01": 2, "gpu-02": 1, "cpu-01": 1, "Unauthenticated": 0,
+    })).unwrap());
+    app.worker_pings = Some(serde_json::from_value(json!({
+        "gpu-01": [ago(20), ago(10)],
+        "gpu-02": [ago(5)],
+        "cpu-01": [],
+    })).unwrap());
+    app.worker_versions = Some(serde_json::from_value(json!({
+        "gpu-01": { "hive": "0.4.2", "ollama": "0.6.5" },
+        "gpu-02": { "hive": "0.4.2", "ollama": "0.6.5" },
+        "cpu-01": { "hive": "0.4.1", "ollama": "0.6.2" },
+    })).unwrap());
+    app.worker_tags = Some(serde_json::from_value(json!({
+        "gpu-01": ["llama3.1:8b", "qwen2.5:7b"],
+        "gpu-02": ["llama3.1:8b"],
+        "cpu-01": ["nomic-embed-text:latest"],
+    })).unwrap());
+    let queue = serde_json::from_value(json!({
+        "Model: llama3.1:8b": 3,
+        "Model: qwen2.5:7b": 1,
+        "Node: gpu-01": 2,
+    })).unwrap();
+    app.history.record_queue(Utc::now(), &queue);
+    app.queue_map = Some(queue);
+    app.auth_keys = Some(serde_json::from_value(json!([
+        { "id": "1", "name": "admin", "role": "Admin", "value": "adm-7f3c" },
+        { "id": "2", "name": "ci", "role": "Client", "value": "cli-91ab" },
+    ])).unwrap());
+
+    for resource in Resource::all() {
+        app.endpoint_health.insert(*resource, EndpointHealth::Ok(Utc::now()));
+    }
+    app.fleet.insert("lab".into(), ClusterState {
+        summary: Some(ClusterSummary { workers: 3, busy: 1, connections: 4, queued: 4 }),
+        error: None,
+        updated: Utc::now(),
+    });
+    app.fleet.insert("staging".into(), ClusterState {
+        summary: None,
+        error: Some("HTTP error: error sending request".into()),
+        updated: Utc::now(),
+    });
+    app.console_output = vec!["Hello from llama3.1:8b.".into(), "How can I help?".into()];
+    app
+}
+
+/// An app replaying two minutes recorded long ago: queues building up on
+/// gpu-01 while it works, and gpu-02 going quiet halfway through
+fn replay_fixture() -> App {
+    let path = std::env::temp_dir().join(format!("hive-snapshot-replay-{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let start = DateTime::parse_from_rfc3339("2025-11-03T14:00:00Z").unwrap().with_timezone(&Utc);
+    let mut recorder = Recorder::create(&path).unwrap();
+    for step in 0..=12i64 {
+        let at = start + TimeDelta::seconds(step * 10);
+        let pings = |until: i64| (0..=step.min(until)).map(|s| start + TimeDelta::seconds(s * 10)).collect::<Vec<_>>();
+        let polled = [
+            PollData::Queue(serde_json::from_value(json!({
+                "Model: llama3.1:8b": step / 2,
+                "Model: qwen2.5:7b": step % 3,
+                "Node: gpu-01": step / 4,
+            })).unwrap()),
+            PollData::Statuses(serde_json::from_value(json!({
+                "gpu-01": (0..=step).map(|s| if s < 3 { "Polling" } else { "Working" }).collect::<Vec<_>>(),
+                "gpu-02": (0..=step).map(|_| "Polling").collect::<Vec<_>>(),
+            })).unwrap()),
+            PollData::Pings(serde_json::from_value(json!({ "gpu-01": pings(12), "gpu-02": pings(6) })).unwrap()),
+            PollData::Connections(serde_json::from_value(json!({ "gpu-01": 2, "gpu-02": 1 })).unwrap()),
+            PollData::Versions(serde_json::from_value(json!({
+                "gpu-01": { "hive": "0.4.2", "ollama": "0.6.5" },
+                "gpu-02": { "hive": "0.4.2", "ollama": "0.6.5" },
+            })).unwrap()),
+        ];
+        for data in &polled {
+            recorder.record(at, "lab", data).unwrap();
+        }
+    }
+
+    let mut app = App::new(vec![Profile::single_port("lab", "", 0)]);
+    app.set_active_profile(0);
+    app.replay = Some(Replay::load(&path).unwrap());
+    std::fs::remove_file(&path).unwrap();
+    app.toggle_replay();
+    app.seek_replay(TimeDelta::seconds(125));
+    app
+}
+
+/// The Dashboard with a pull or delete underway
+fn action(state: ActionPanelState, focus: Focus) -> App {
+    let mut app = fixture();
+    app.action_panel_state = state;
+    app.focus = focus;
+    app
+}
+
+/// Draw `app` like the terminal loop does
+fn render(app: &App, width: u16, height: u16) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(|f| super::draw(f, app)).unwrap();
+    buffer_text(terminal.backend().buffer())
+}
+
+/// One line per row, without trailing blanks
+fn buffer_text(buffer: &Buffer) -> String {
+    let width = buffer.area.width as usize;
+    buffer.content
+        .chunks(width)
+        .map(|row| {
+            let line: String = row.iter().map(|cell| cell.symbol()).collect();
+            format!("{}\n", mask_timestamps(line.trim_end()))
+        })
+        .collect()
+}
+
+/// Replace the digits of RFC 3339 timestamps, including ones cut off by a
+/// narrow column, and of `HH:MM:SS` clock times with `#`
+fn mask_timestamps(line: &str) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let digits = |from: usize, n: usize| chars.len() >= from + n && chars[from..from + n].iter().all(char::is_ascii_digit);
+    let starts_timestamp = |i: usize| {
+        let date = digits(i, 4) && chars.get(i + 4) == Some(&'-') && digits(i + 5, 1);
+        let clock = digits(i, 2) && chars.get(i + 2) == Some(&':') && digits(i + 3, 2)
+            && chars.get(i + 5) == Some(&':') && digits(i + 6, 2);
+        (date || clock) && (i == 0 || !chars[i - 1].is_ascii_digit())
+    };
+    let mut out = String::with_capacity(line.len());
+    let mut i = 0;
+    while i < chars.len() {
+        if starts_timestamp(i) {
+            while i < chars.len() && (chars[i].is_ascii_digit() || "-:T.+Z".contains(chars[i])) {
+                out.push(if chars[i].is_ascii_digit() { '#' } else { chars[i] });
+                i += 1;
+            }
+        } else {
+            out.push(chars[i]);
+            i += 1;
+        }
+    }
+    out
+}
+
+/// Compare `app` at every size with the stored snapshots of `case`
+fn check(case: &str, app: &App) {
+    let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+    let mut failures = Vec::new();
+    for (width, height) in SIZES {
+        let name = format!("{}_{}x{}", case, width, height);
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/ui/snapshots").join(format!("{}.txt", name));
+        let actual = render(app, width, height);
+        let position = Tab::all().iter().position(|t| *t == app.current_tab).unwrap();
+        let active = format!("{} {}", position + 1, app.current_tab.title());
+        if !actual.lines().next().unwrap_or_default().contains(&active) {
+            failures.push(format!("{} does not show the active tab \"{}\" in the tab bar", name, active));
+            continue;
+        }
+        if update {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, &actual).unwrap();
+            continue;
+        }
+        match std::fs::read_to_string(&path) {
+            Ok(expected) if expected == actual => {}
+            Ok(expected) => failures.push(format!("{} differs:\n{}", name, diff(&expected, &actual))),
+            Err(_) => failures.push(format!("{} is missing; run with UPDATE_SNAPSHOTS=1 to create it", name)),
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+/// Changed lines as `-expected` / `+actual` pairs
+fn diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    (0..expected.len().max(actual.len()))
+        .filter(|&i| expected.get(i) != actual.get(i))
+        .map(|i| format!("{:>3} -{}\n    +{}", i + 1, expected.get(i).unwrap_or(&""), actual.get(i).unwrap_or(&"")))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn tab(tab: Tab) -> App {
+    let mut app = fixture();
+    app.current_tab = tab;
+    app
+}
+
+#[test]
+fn dashboard() {
+    check("dashboard", &tab(Tab::Dashboard));
+}
+
+#[test]
+fn dashboard_loading() {
+    let mut app = App::new(vec![Profile::single_port("lab", "http://hive.lab", 6668)]);
+    app.set_active_profile(0);
+    check("dashboard_loading", &app);
+}
+
+#[test]
+fn dashboard_offline() {
+    let mut app = tab(Tab::Dashboard);
+    let since = Utc::now();
+    for resource in Resource::all() {
+        app.endpoint_health.insert(*resource, EndpointHealth::Unreachable(since));
+    }
+    check("dashboard_offline", &app);
+}
+
+#[test]
+fn dashboard_degraded() {
+    let mut app = tab(Tab::Dashboard);
+    for resource in [Resource::Statuses, Resource::Connections] {
+        app.endpoint_health.insert(resource, EndpointHealth::Failing("HiveCore internal error (HTTP 500)".into()));
+    }
+    check("dashboard_degraded", &app);
+}
+
+#[test]
+fn nodes() {
+    check("nodes", &tab(Tab::Nodes));
+}
+
+#[test]
+fn queues() {
+    check("queues", &tab(Tab::Queues));
+}
+
+#[test]
+fn keys() {
+    check("keys", &tab(Tab::Keys));
+}
+
+#[test]
+fn logs() {
+    check("logs", &tab(Tab::Logs));
+}
+
+#[test]
+fn console() {
+    check("console", &tab(Tab::Console));
+}
+
+#[test]
+fn fleet() {
+    check("fleet", &tab(Tab::Fleet));
+}
+
+#[test]
+fn alerts() {
+    check("alerts", &tab(Tab::Alerts));
+}
+
+#[test]
+fn models() {
+    check("models", &tab(Tab::Models));
+}
+
+#[test]
+fn models_scrolled() {
+    let mut app = tab(Tab::Models);
+    let models: Vec<String> = (1..=30).map(|i| format!("model-{:02}:latest", i)).collect();
+    let workers: Vec<String> = (1..=12).map(|i| format!("node-{:02}", i)).collect();
+    app.worker_statuses = Some(workers.iter().map(|w| (w.clone(), vec!["Polling".to_string()])).collect());
+    app.worker_tags = Some(workers.iter().enumerate()
+        .map(|(i, w)| (w.clone(), models.iter().step_by(i + 1).cloned().collect()))
+        .collect());
+    app.models_cursor = (27, 10);
+    check("models_scrolled", &app);
+}
+
+#[test]
+fn replay_queues() {
+    let mut app = replay_fixture();
+    app.current_tab = Tab::Queues;
+    check("replay_queues", &app);
+}
+
+#[test]
+fn replay_nodes() {
+    let mut app = replay_fixture();
+    app.current_tab = Tab::Nodes;
+    check("replay_nodes", &app);
+}
+
+#[test]
+fn replay_worker_detail() {
+    let mut app = replay_fixture();
+    app.worker_detail = Some("gpu-02".into());
+    check("replay_worker_detail", &app);
+}
+
+#[test]
+fn action_pull_input() {
+    let mut app = action(ActionPanelState::PullModel, Focus::ActionPanelInput);
+    app.action_input_model_name = "phi3:mini".into();
+    app.action_input_cursor_position = 9;
+    check("action_pull_input", &app);
+}
+
+#[test]
+fn action_delete_input_on_marked_workers() {
+    let mut app = action(ActionPanelState::DeleteModel, Focus::ActionPanelInput);
+    app.marked_workers.extend(["gpu-01".to_string(), "gpu-02".to_string()]);
+    app.action_target = ActionTarget::Marked;
+    app.action_input_model_name = "llama3.1:8b".into();
+    app.action_input_cursor_position = 11;
+    check("action_delete_input_marked", &app);
+}
+
+#[test]
+fn action_confirmation() {
+    let app = action(ActionPanelState::Confirmation("phi3:mini".into(), ActionType::Pull), Focus::ActionPanelConfirm);
+    check("action_confirmation", &app);
+}
+
+#[test]
+fn action_pull_response_in_progress() {
+    let lines = vec!["pulling manifest".to_string(), "pulling 6a0746a1ec1a".to_string()];
+    let mut app = action(
+        ActionPanelState::Response("phi3:mini".into(), ActionType::Pull, lines, true),
+        Focus::ActionPanelResponse,
+    );
+    app.is_action_in_progress = true;
+    // Two updates a second apart give each layer a known rate
+    let start = Instant::now();
+    for (seconds, done) in [(0, 0), (1, 1)] {
+        for (digest, total, per_second) in [("sha256:6a0746a1ec1a", 2_000_000_000u64, 50_000_000u64), ("sha256:4fa551d4f938", 12_403, 12_403)] {
+            let line = PullStatus {
+                status: format!("pulling {}", &digest[7..]),
+                digest: Some(digest.into()),
+                total: Some(total),
+                completed: Some(per_second * done),
+                ..PullStatus::default()
+            };
+            app.pull_progress.update(&line, start + Duration::from_secs(seconds));
+        }
+    }
+    check("action_pull_response_progress", &app);
+}
+
+#[test]
+fn action_delete_response_failed() {
+    let lines = vec!["Delete failed: model 'phi3:mini' not found".to_string()];
+    let app = action(
+        ActionPanelState::Response("phi3:mini".into(), ActionType::Delete, lines, false),
+        Focus::ActionPanelResponse,
+    );
+    check("action_delete_response_failed", &app);
+}
+
+#[test]
+fn action_batch() {
+    let mut app = action(ActionPanelState::Batch("phi3:mini".into(), ActionType::Pull), Focus::ActionPanelResponse);
+    app.is_action_in_progress = true;
+    app.batch = vec![
+        BatchRow { worker: "cpu-01".into(), state: BatchState::Done("success".into()) },
+        BatchRow { worker: "gpu-01".into(), state: BatchState::Running("40% of 1.9 GiB at 48.0 MiB/s".into()) },
+        BatchRow { worker: "gpu-02".into(), state: BatchState::Failed("worker 'gpu-02' not found".into()) },
+        BatchRow { worker: "gpu-03".into(), state: BatchState::Queued },
+    ];
+    check("action_batch", &app);
+}
+
+#[test]
+fn overlays_fit_tiny_terminals() {
+    let mut app = tab(Tab::Console);
+    app.model_picker = Some(ModelPicker {
+        stage: PickerStage::Model,
+        models: vec!["llama3.1:8b".into(), "qwen2.5:7b".into()],
+        nodes: Vec::new(),
+        selected: 0,
+    });
+    for (width, height) in [(1, 1), (20, 4), (30, 6), (40, 7)] {
+        render(&app, width, height);
+    }
+    app.model_picker = None;
+    app.open_profiles_view();
+    for (width, height) in [(1, 1), (20, 4), (30, 6)] {
+        render(&app, width, height);
+    }
+}
@@ -0,0 +1,79 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+};
+use chrono::Local;
+use crate::app::App;
+
+/// Draw the Alerts tab: active alerts first, then recently resolved ones
+pub fn draw(f: &mut Frame, area: Rect, app: &App) {
+    let title = format!(
+        "Alerts — {} rules, {} firing",
+        app.alerts.rules.len(),
+        app.alerts.active.len()
+    );
+    let block = Block::default().title(title).borders(Borders::ALL);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    let hint = Paragraph::new("UP/DOWN select | a acknowledge | m silence 1h / unsilence | rules: alerts.toml next to profiles.toml")
+        .style(Style::default().fg(Color::DarkGray));
+    f.render_widget(hint, chunks[1]);
+
+    let listed = app.alerts.listed();
+    if listed.is_empty() {
+        let text = if app.alerts.rules.is_empty() {
+            "No alert rules configured."
+        } else {
+            "No alerts."
+        };
+        f.render_widget(Paragraph::new(text), chunks[0]);
+        return;
+    }
+
+    let now = app.now();
+    let rows: Vec<Row> = listed.iter().map(|alert| {
+        let silenced = app.alerts.is_silenced(&alert.id, now);
+        let (state, style) = match (alert.resolved_at, alert.acked, silenced) {
+            (Some(_), _, _) => ("resolved", Style::default().fg(Color::DarkGray)),
+            (None, _, true) => ("silenced", Style::default().fg(Color::DarkGray)),
+            (None, true, _) => ("acked", Style::default().fg(Color::Yellow)),
+            (None, false, _) => ("FIRING", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+        };
+        let when = alert.resolved_at.unwrap_or(alert.fired_at)
+            .with_timezone(&Local)
+            .format("%H:%M:%S")
+            .to_string();
+        Row::new(vec![
+            Cell::from(state),
+            Cell::from(alert.rule.clone()),
+            Cell::from(alert.subject.clone()),
+            Cell::from(alert.message.clone()),
+            Cell::from(when),
+        ]).style(style)
+    }).collect();
+
+    let header = Row::new(vec!["State", "Rule", "Subject", "Message", "Since"])
+        .style(Style::default().add_modifier(Modifier::BOLD));
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(9),
+            Constraint::Percentage(20),
+            Constraint::Percentage(15),
+            Constraint::Min(20),
+            Constraint::Length(9),
+        ],
+    )
+    .header(header)
+    .row_highlight_style(Style::default().fg(Color::Black).bg(Color::LightCyan));
+    let mut state = TableState::default().with_selected(Some(app.alerts_selected.min(listed.len() - 1)));
+    f.render_stateful_widget(table, chunks[0], &mut state);
+}
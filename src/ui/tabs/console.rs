@@ -1,41 +1,187 @@
 use ratatui::{
-    backend::Backend,
     Frame,
-    layout::{Constraint, Direction, Layout},
-    widgets::{Block, Borders, Paragraph, List, ListItem}
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph}
 };
-use crate::app::App;
+use crate::app::{App, ConsoleMode, ModelPicker, PickerStage};
+use crate::ui::centered_rect;
+
+const COLOR_USER: Color = Color::Cyan;
+const COLOR_ASSISTANT: Color = Color::Green;
+const COLOR_SYSTEM: Color = Color::Yellow;
+const COLOR_HINT: Color = Color::DarkGray;
 
 /// Draw the Console (Inference) tab
-pub fn draw(f: &mut Frame, app: &App) {
-    let area = f.size();
-    let block = Block::default().title("Console").borders(Borders::ALL);
+pub fn draw(f: &mut Frame, area: Rect, app: &App) {
+    let mode = match app.console_mode {
+        ConsoleMode::Generate => "Generate",
+        ConsoleMode::Chat => "Chat",
+    };
+    let block = Block::default().title(format!("Console [{}]", mode)).borders(Borders::ALL);
     f.render_widget(block, area);
 
-    // Split into prompt input (3 lines) and output
+    // Split into prompt input (3 lines), output and a key hint line
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(3), Constraint::Min(0)].as_ref())
+        .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(1)].as_ref())
         .margin(1)
         .split(area);
 
-    // Prompt area (show current prompt or placeholder)
-    let prompt_text = if let Some(resp) = &app.generate_response {
-        resp.result.clone()
+    // Prompt area (show current input or placeholder)
+    let prompt_title = if app.editing_system_prompt {
+        "System prompt".to_string()
+    } else {
+        let model = app.console_model().unwrap_or_else(|| "no model".into());
+        let node = app.console_node.as_deref().unwrap_or("any worker");
+        format!("Prompt — {} @ {}", model, node)
+    };
+    let prompt_text = if app.console_input.is_empty() && !app.editing_system_prompt {
+        Span::styled("<Enter prompt and press Enter>", Style::default().fg(COLOR_HINT))
     } else {
-        "<Enter prompt and press Enter>".into()
+        Span::raw(app.console_input.as_str())
     };
-    let prompt = Paragraph::new(prompt_text)
-        .block(Block::default().title("Prompt").borders(Borders::ALL));
+    let prompt = Paragraph::new(Line::from(prompt_text))
+        .block(Block::default().title(prompt_title).borders(Borders::ALL));
     f.render_widget(prompt, chunks[0]);
 
-    // Output area: list of lines from console_output
-    let items: Vec<ListItem> = if app.console_output.is_empty() {
-        vec![ListItem::new("<No output>")]
+    match app.console_mode {
+        ConsoleMode::Generate => draw_generate_output(f, chunks[1], app),
+        ConsoleMode::Chat => draw_conversation(f, chunks[1], app),
+    }
+
+    let hint = match app.console_mode {
+        ConsoleMode::Generate => "F2 chat mode | F3 model/worker | Enter send | Esc clear/cancel | PgUp/PgDn scroll | Tab switch tab",
+        ConsoleMode::Chat => "F2 generate mode | F3 model/worker | ^S system prompt | ^L clear | ^F fork | ^N/^P next/prev | PgUp/PgDn scroll",
+    };
+    f.render_widget(Paragraph::new(hint).style(Style::default().fg(COLOR_HINT)), chunks[2]);
+
+    if let Some(picker) = &app.model_picker {
+        draw_model_picker(f, area, picker);
+    }
+}
+
+fn draw_model_picker(f: &mut Frame, area: Rect, picker: &ModelPicker) {
+    let (title, rows): (&str, Vec<String>) = match picker.stage {
+        PickerStage::Model => ("Select model", picker.models.clone()),
+        PickerStage::Node => (
+            "Pin to worker",
+            std::iter::once("<any worker>".to_string())
+                .chain(picker.nodes.iter().cloned())
+                .collect(),
+        ),
+    };
+    let height = (rows.len() as u16).saturating_add(3).max(5).min(area.height.saturating_sub(2));
+    let popup = centered_rect(area, 50, height);
+    f.render_widget(Clear, popup);
+
+    let items: Vec<ListItem> = if rows.is_empty() {
+        vec![ListItem::new(Span::styled("No models known yet", Style::default().fg(COLOR_HINT)))]
+    } else {
+        rows.into_iter().map(ListItem::new).collect()
+    };
+    let list = List::new(items)
+        .block(Block::default()
+            .title(format!("{} (Enter choose, ESC close)", title))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(COLOR_USER)))
+        .highlight_style(Style::default().fg(Color::Black).bg(Color::LightCyan));
+    let mut state = ListState::default().with_selected(Some(picker.selected));
+    f.render_stateful_widget(list, popup, &mut state);
+}
+
+fn draw_generate_output(f: &mut Frame, area: Rect, app: &App) {
+    let block = Block::default().title(format!("Output{}", stats_suffix(app))).borders(Borders::ALL);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    // Output area: wrapped lines from console_output, following the stream
+    let width = inner.width.max(1) as usize;
+    let lines: Vec<Line> = if app.console_output.is_empty() {
+        vec![Line::from(Span::styled("<No output>", Style::default().fg(COLOR_HINT)))]
     } else {
-        app.console_output.iter().map(|l| ListItem::new(l.clone())).collect()
+        app.console_output.iter()
+            .flat_map(|l| wrap(l, width))
+            .map(Line::from)
+            .collect()
+    };
+    let overflow = lines.len().saturating_sub(inner.height as usize) as u16;
+    let offset = overflow.saturating_sub(app.console_scroll);
+    f.render_widget(Paragraph::new(lines).scroll((offset, 0)), inner);
+}
+
+/// " — 42 tok, 12.3 tok/s" for the current or last stream
+fn stats_suffix(app: &App) -> String {
+    let Some(stats) = &app.generation_stats else {
+        return String::new();
+    };
+    let state = if app.is_generating { " streaming" } else { "" };
+    if app.is_generating && stats.first_token.is_none() {
+        return format!(" — waiting {}s", stats.started.elapsed().as_secs());
+    }
+    match stats.tokens_per_second() {
+        Some(tps) => format!(" —{} {} tok, {:.1} tok/s", state, stats.tokens, tps),
+        None => format!(" —{} {} tok", state, stats.tokens),
+    }
+}
+
+fn draw_conversation(f: &mut Frame, area: Rect, app: &App) {
+    let conversation = app.conversation();
+    let title = format!(
+        "Conversation {}/{}{}",
+        app.active_conversation + 1,
+        app.conversations.len(),
+        stats_suffix(app)
+    );
+    let block = Block::default().title(title).borders(Borders::ALL);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let width = inner.width.max(1) as usize;
+    let mut lines: Vec<Line> = Vec::new();
+    if !conversation.system_prompt.is_empty() {
+        push_message(&mut lines, "system", &conversation.system_prompt, width);
+    }
+    for message in &conversation.messages {
+        push_message(&mut lines, &message.role, &message.content, width);
+    }
+    if app.is_generating {
+        lines.push(Line::from(Span::styled("generating… (ESC to cancel)", Style::default().fg(COLOR_HINT))));
+    }
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled("<No messages>", Style::default().fg(COLOR_HINT))));
+    }
+
+    // Stick to the bottom unless the user scrolled up
+    let overflow = lines.len().saturating_sub(inner.height as usize) as u16;
+    let offset = overflow.saturating_sub(app.console_scroll);
+    f.render_widget(Paragraph::new(lines).scroll((offset, 0)), inner);
+}
+
+fn push_message(lines: &mut Vec<Line>, role: &str, content: &str, width: usize) {
+    let color = match role {
+        "user" => COLOR_USER,
+        "assistant" => COLOR_ASSISTANT,
+        _ => COLOR_SYSTEM,
     };
-    let output = List::new(items)
-        .block(Block::default().title("Output").borders(Borders::ALL));
-    f.render_widget(output, chunks[1]);
-}
\ No newline at end of file
+    lines.push(Line::from(Span::styled(
+        format!("{}:", role),
+        Style::default().fg(color).add_modifier(Modifier::BOLD),
+    )));
+    for text_line in content.lines() {
+        for chunk in wrap(text_line, width.saturating_sub(2).max(1)) {
+            lines.push(Line::from(Span::styled(format!("  {}", chunk), Style::default().fg(color))));
+        }
+    }
+    lines.push(Line::from(""));
+}
+
+/// Hard-wrap a line at `width` characters so the scroll offset can be computed
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    if text.is_empty() {
+        return vec![String::new()];
+    }
+    let chars: Vec<char> = text.chars().collect();
+    chars.chunks(width).map(|c| c.iter().collect()).collect()
+}
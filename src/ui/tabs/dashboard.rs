@@ -1,13 +1,16 @@
 // src/ui/tabs/dashboard.rs
 use ratatui::{
-    backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, Cell, Gauge, List, ListItem, Paragraph, Row, Sparkline, Table, Wrap},
     Frame,
 };
-use crate::app::{ActionPanelState, ActionType, App, Focus};
+use crate::app::{ActionPanelState, ActionTarget, ActionType, App, BatchState, ConnectionStatus, Focus, Resource};
+use crate::history::{tail_values, Sample};
+use crate::models::PullProgress;
+use crate::utils::format;
+use std::collections::VecDeque;
 
 // --- Color Scheme Definitions ---
 const COLOR_DEFAULT_FG: Color = Color::White;
@@ -20,12 +23,25 @@ const COLOR_SELECTED_INACTIVE_FOCUS_FG: Color = Color::LightCyan;
 const COLOR_STATUS_GOOD: Color = Color::Green; // For "Polling" or active connections (Green in diagram)
 const COLOR_STATUS_BAD: Color = Color::Red;    // For "Working" or problematic (Red in diagram)
 const COLOR_CATEGORY_TITLE: Color = Color::Yellow;
+/// Most layer gauges shown above a pull's log
+const MAX_LAYER_GAUGES: usize = 6;
 
 
-pub fn draw(f: &mut Frame, app: &App) {
-    let size = f.size();
+pub fn draw(f: &mut Frame, size: Rect, app: &App) {
+    if app.worker_detail.is_some() {
+        crate::ui::worker_detail::draw(f, size, app);
+        return;
+    }
+
+    let mut title = vec![Span::styled("Hive Monitor", Style::default().fg(COLOR_BORDER))];
+    if let ConnectionStatus::Offline(since) = app.connection_status() {
+        title.push(Span::styled(
+            format!(" HiveCore unreachable since {} ", since.with_timezone(&chrono::Local).format("%H:%M:%S")),
+            Style::default().fg(COLOR_STATUS_BAD).add_modifier(Modifier::BOLD),
+        ));
+    }
     let outer = Block::default()
-        .title(Span::styled("Hive Monitor", Style::default().fg(COLOR_BORDER)))
+        .title(Line::from(title))
         .borders(Borders::ALL)
         .border_style(Style::default().fg(COLOR_BORDER));
     f.render_widget(outer, size);
@@ -60,6 +76,9 @@ pub fn draw(f: &mut Frame, app: &App) {
         ActionPanelState::Response(model_name, action_type, result, is_success) => {
             draw_model_response_panel(f, cols[2], app, model_name, *action_type, result, is_success);
         }
+        ActionPanelState::Batch(model_name, action_type) => {
+            draw_batch_panel(f, cols[2], app, model_name, *action_type);
+        }
     }
 }
 
@@ -76,7 +95,12 @@ fn draw_workers_list(f: &mut Frame, area: Rect, app: &App) {
                 .and_then(|m| m.get(name))
                 .copied()
                 .unwrap_or(0);
-            let label = format!("{} ({})", name, conns);
+            let label = if app.marked_workers.is_empty() {
+                format!("{} ({})", name, conns)
+            } else {
+                let mark = if app.marked_workers.contains(name) { "[x]" } else { "[ ]" };
+                format!("{} {} ({})", mark, name, conns)
+            };
             let style = if app.focus == Focus::WorkersList && i == app.selected_worker {
                 Style::default().fg(COLOR_HIGHLIGHT_FG).bg(COLOR_HIGHLIGHT_BG)
             } else if (app.focus == Focus::ActionsList || app.focus == Focus::GlobalView) && i == app.selected_worker {
@@ -93,7 +117,11 @@ fn draw_workers_list(f: &mut Frame, area: Rect, app: &App) {
         items.push(ListItem::new(format!("Unauthenticated ({})", cnt)).style(Style::default().fg(Color::DarkGray)));
     }
 
-    let block_title = Span::styled("Workers", Style::default().fg(COLOR_BORDER));
+    let title = match app.marked_workers.len() {
+        0 => "Workers".to_string(),
+        n => format!("Workers ({} marked)", n),
+    };
+    let block_title = Span::styled(title, Style::default().fg(COLOR_BORDER));
     let block_style = Style::default().fg(COLOR_DEFAULT_FG).bg(COLOR_DEFAULT_BG);
     let block = Block::default()
         .title(block_title)
@@ -149,7 +177,7 @@ fn draw_actions_panel(f: &mut Frame, area: Rect, app: &App) {
                     lines.push(Line::from("  No version info"));
                 }
             } else {
-                lines.push(Line::from("  Loading versions..."));
+                lines.push(Line::from(format!("  {}", app.placeholder(Resource::Versions))));
             }
 
             lines.push(Line::from(Span::styled("Last Ping:", Style::default().add_modifier(Modifier::BOLD))));
@@ -164,7 +192,7 @@ fn draw_actions_panel(f: &mut Frame, area: Rect, app: &App) {
                     lines.push(Line::from("  No ping data for worker"));
                 }
             } else {
-                lines.push(Line::from("  Loading pings..."));
+                lines.push(Line::from(format!("  {}", app.placeholder(Resource::Pings))));
             }
 
             lines.push(Line::from(Span::styled("Models:", Style::default().add_modifier(Modifier::BOLD))));
@@ -181,7 +209,21 @@ fn draw_actions_panel(f: &mut Frame, area: Rect, app: &App) {
                     lines.push(Line::from("  No model tags for worker"));
                 }
             } else {
-                lines.push(Line::from("  Loading models..."));
+                lines.push(Line::from(format!("  {}", app.placeholder(Resource::Tags))));
+            }
+
+            lines.push(Line::from(Span::styled("Status changes:", Style::default().add_modifier(Modifier::BOLD))));
+            let mut transitions = app.history.transitions_of(name).take(3).peekable();
+            if transitions.peek().is_none() {
+                lines.push(Line::from("  No changes recorded"));
+            }
+            for t in transitions {
+                lines.push(Line::from(format!(
+                    "  {} {} -> {}",
+                    t.at.with_timezone(&chrono::Local).format("%H:%M:%S"),
+                    t.from.as_deref().unwrap_or("new"),
+                    t.to.as_deref().unwrap_or("gone"),
+                )));
             }
 
             Paragraph::new(lines).block(Block::default()
@@ -197,7 +239,7 @@ fn draw_actions_panel(f: &mut Frame, area: Rect, app: &App) {
                 .style(Style::default().fg(COLOR_DEFAULT_FG).bg(COLOR_DEFAULT_BG)))
         }
     } else {
-        Paragraph::new("Loading worker statuses...").block(Block::default()
+        Paragraph::new(app.placeholder(Resource::Statuses)).wrap(Wrap { trim: true }).block(Block::default()
             .title(Span::styled(&info_block_title, Style::default().fg(COLOR_BORDER)))
             .borders(Borders::ALL)
             .border_style(Style::default().fg(COLOR_BORDER))
@@ -213,11 +255,12 @@ fn draw_actions_panel(f: &mut Frame, area: Rect, app: &App) {
 fn draw_global_view(f: &mut Frame, area: Rect, app: &App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Percentage(70), Constraint::Percentage(30)].as_ref())
+        .constraints([Constraint::Min(0), Constraint::Length(5), Constraint::Percentage(30)].as_ref())
         .split(area);
 
     let worker_grid_area = chunks[0];
-    let queues_area = chunks[1];
+    let trends_area = chunks[1];
+    let queues_area = chunks[2];
 
     let worker_grid_block = Block::default()
         .title(Span::styled("Workers Busy", Style::default().fg(COLOR_BORDER)))
@@ -358,11 +401,18 @@ fn draw_global_view(f: &mut Frame, area: Rect, app: &App) {
             }
         }
     } else {
-        let loading = Paragraph::new("Loading worker statuses...")
+        let loading = Paragraph::new(app.placeholder(Resource::Statuses)).wrap(Wrap { trim: true })
             .block(Block::default().title(Span::styled("Workers Busy", Style::default().fg(COLOR_BORDER))).borders(Borders::ALL).border_style(Style::default().fg(COLOR_BORDER)));
         f.render_widget(loading, worker_grid_inner_area);
     }
 
+    let trend_cols = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+        .split(trends_area);
+    draw_trend(f, trend_cols[0], "Connections", &app.history.total_connections, COLOR_STATUS_GOOD);
+    draw_trend(f, trend_cols[1], "Queued", &app.history.total_queued, COLOR_CATEGORY_TITLE);
+
     let queues_block = Block::default()
         .title(Span::styled("Queues", Style::default().fg(COLOR_BORDER)))
         .borders(Borders::ALL)
@@ -388,10 +438,10 @@ fn draw_global_view(f: &mut Frame, area: Rect, app: &App) {
 
         for name in sorted_queue_names {
             let cnt = queues.get(name).unwrap();
-            if name.starts_with("Model:") {
-                model_lines.push(Line::from(name.replace("Model: ", "").to_string()));
-            } else if name.starts_with("Node:") {
-                worker_lines.push(Line::from(format!("{}: {}", name.replace("Node: ", ""), cnt)));
+            if let Some(model) = name.strip_prefix("Model: ") {
+                model_lines.push(Line::from(format!("{}: {}", model, cnt)));
+            } else if let Some(node) = name.strip_prefix("Node: ") {
+                worker_lines.push(Line::from(format!("{}: {}", node, cnt)));
             } else {
                 model_lines.push(Line::from(format!("{}: {}", name, cnt)));
             }
@@ -416,6 +466,128 @@ fn draw_global_view(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(worker_paragraph, worker_queues_area);
 }
 
+/// Sparkline of the most recent samples of `series`, titled with the latest value and peak
+fn draw_trend(f: &mut Frame, area: Rect, title: &str, series: &VecDeque<Sample>, color: Color) {
+    let width = area.width.saturating_sub(2) as usize;
+    let values = tail_values(series, width);
+    let title = match values.last() {
+        Some(now) => format!("{} {} (peak {})", title, now, values.iter().max().unwrap_or(now)),
+        None => format!("{} (no history)", title),
+    };
+    let sparkline = Sparkline::default()
+        .block(Block::default()
+            .title(Span::styled(title, Style::default().fg(COLOR_BORDER)))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(COLOR_BORDER)))
+        .data(&values)
+        .style(Style::default().fg(color).bg(COLOR_DEFAULT_BG));
+    f.render_widget(sparkline, area);
+}
+
+
+/// Overall gauge plus one gauge per layer, unfinished layers first
+fn draw_pull_gauges(f: &mut Frame, area: Rect, progress: &PullProgress) {
+    let mut layers: Vec<_> = progress.layers.iter().collect();
+    layers.sort_by_key(|l| l.is_done());
+    layers.truncate(MAX_LAYER_GAUGES);
+
+    let mut constraints = vec![Constraint::Length(1); layers.len() + 1];
+    constraints.push(Constraint::Length(1)); // Gap before the log
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(area);
+
+    let (completed, total) = progress.totals();
+    let rate = progress.rate();
+    let state = if completed >= total {
+        "done".to_string()
+    } else if let Some(eta) = progress.eta() {
+        format!("{}/s ETA {}", format::bytes(rate as u64), format::eta(eta))
+    } else if rate > 0.0 {
+        "stalled".to_string()
+    } else {
+        "starting".to_string()
+    };
+    let overall = Gauge::default()
+        .gauge_style(Style::default().fg(COLOR_STATUS_GOOD).bg(Color::DarkGray))
+        .ratio((completed as f64 / total.max(1) as f64).min(1.0))
+        .label(format!("total {} / {}  {}", format::bytes(completed), format::bytes(total), state));
+    f.render_widget(overall, rows[0]);
+
+    for (layer, row) in layers.iter().zip(rows.iter().skip(1)) {
+        let digest = layer.digest.trim_start_matches("sha256:");
+        let state = if layer.is_done() {
+            "done".to_string()
+        } else {
+            let eta = layer.eta().map(|d| format!(" ETA {}", format::eta(d))).unwrap_or_default();
+            format!("{}/s{}", format::bytes(layer.rate as u64), eta)
+        };
+        let gauge = Gauge::default()
+            .gauge_style(Style::default().fg(COLOR_BORDER).bg(Color::DarkGray))
+            .ratio(layer.fraction())
+            .label(format!(
+                "{:.12}  {} / {}  {}",
+                digest, format::bytes(layer.completed), format::bytes(layer.total), state,
+            ));
+        f.render_widget(gauge, *row);
+    }
+}
+
+/// "on <worker>" or "on <target> (N workers)" for the confirmation panel
+fn target_summary(app: &App, model_name: &str, action_type: ActionType) -> String {
+    let workers = app.target_workers(model_name, action_type);
+    match app.action_target {
+        ActionTarget::Selected => format!("on {}", workers.first().map(String::as_str).unwrap_or("any worker")),
+        target => format!("on {} ({} workers)", target.label(action_type), workers.len()),
+    }
+}
+
+fn draw_batch_panel(f: &mut Frame, area: Rect, app: &App, model_name: &str, action_type: ActionType) {
+    let action_verb = match action_type {
+        ActionType::Pull => "Pull",
+        ActionType::Delete => "Delete",
+    };
+    let done = app.batch.iter().filter(|r| matches!(r.state, BatchState::Done(_))).count();
+    let failed = app.batch.iter().filter(|r| matches!(r.state, BatchState::Failed(_))).count();
+    let title = format!(
+        "{} {}: {}/{} done, {} failed (max {} at once)",
+        action_verb, model_name, done, app.batch.len(), failed, app.action_parallelism,
+    );
+
+    let block = Block::default()
+        .title(Span::styled(title, Style::default().fg(COLOR_BORDER)))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if failed > 0 { COLOR_STATUS_BAD } else { COLOR_BORDER }))
+        .style(Style::default().fg(COLOR_DEFAULT_FG).bg(COLOR_DEFAULT_BG));
+    let inner_area = block.inner(area);
+    f.render_widget(block, area);
+
+    let parts = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)].as_ref())
+        .split(inner_area);
+
+    let rows: Vec<Row> = app.batch.iter().map(|row| {
+        let (state, detail, color) = match &row.state {
+            BatchState::Queued => ("queued", "", Color::DarkGray),
+            BatchState::Running(status) => ("running", status.as_str(), Color::Yellow),
+            BatchState::Done(status) => ("done", status.as_str(), COLOR_STATUS_GOOD),
+            BatchState::Failed(error) => ("failed", error.as_str(), COLOR_STATUS_BAD),
+        };
+        Row::new(vec![
+            Cell::from(row.worker.clone()),
+            Cell::from(state).style(Style::default().fg(color)),
+            Cell::from(detail.to_string()),
+        ])
+    }).collect();
+    let table = Table::new(rows, [Constraint::Percentage(30), Constraint::Length(8), Constraint::Min(0)])
+        .header(Row::new(vec!["Worker", "State", "Detail"]).style(Style::default().add_modifier(Modifier::BOLD)));
+    f.render_widget(table, parts[0]);
+
+    let hint = if app.is_action_in_progress { "Any key aborts and dismisses" } else { "Any key to dismiss" };
+    f.render_widget(Paragraph::new(hint).style(Style::default().fg(Color::DarkGray)), parts[1]);
+}
 
 // --- New drawing functions for Action Panel ---
 
@@ -440,6 +612,7 @@ fn draw_action_input_panel(f: &mut Frame, area: Rect, app: &App, action_type: Ac
         .constraints([
             Constraint::Length(1), // Prompt "Model Name:"
             Constraint::Length(3), // Input box (with borders)
+            Constraint::Length(1), // Target workers
             Constraint::Min(0),    // Spacer
             Constraint::Length(1), // Instructions
             Constraint::Length(1), // ESC instruction
@@ -449,8 +622,9 @@ fn draw_action_input_panel(f: &mut Frame, area: Rect, app: &App, action_type: Ac
 
     let prompt_area = main_layout[0];
     let input_area = main_layout[1];
-    let instructions_area = main_layout[3];
-    let esc_instruction_area = main_layout[4];
+    let target_area = main_layout[2];
+    let instructions_area = main_layout[4];
+    let esc_instruction_area = main_layout[5];
 
     // Prompt text
     f.render_widget(Paragraph::new(format!("{} Model Name:", action_verb))
@@ -473,6 +647,12 @@ fn draw_action_input_panel(f: &mut Frame, area: Rect, app: &App, action_type: Ac
         );
     }
 
+    f.render_widget(Paragraph::new(Line::from(vec![
+        Span::raw("Target: "),
+        Span::styled(app.action_target.label(action_type), Style::default().fg(Color::Yellow)),
+        Span::styled("  (TAB to change)", Style::default().fg(Color::DarkGray)),
+    ])), target_area);
+
     // Instructions
     f.render_widget(Paragraph::new(Line::from("Type model name, press ENTER to confirm."))
         .style(Style::default().fg(Color::DarkGray)), instructions_area);
@@ -499,6 +679,7 @@ fn draw_model_confirmation_panel(f: &mut Frame, area: Rect, app: &App, model_nam
     let text = vec![
         Line::from(format!("Are you sure you want to {} model:", action_verb)),
         Line::from(Span::styled(format!("  {}", model_name), Style::default().add_modifier(Modifier::BOLD).fg(Color::Yellow))),
+        Line::from(target_summary(app, model_name, action_type)),
         Line::from(""),
     ];
 
@@ -573,7 +754,17 @@ fn draw_model_response_panel(f: &mut Frame, area: Rect, app: &App, model_name: &
         .style(block_style);
     f.render_widget(&block, area);
 
-    let inner_area = block.inner(area);
+    let mut inner_area = block.inner(area);
+
+    if action_type == ActionType::Pull && !app.pull_progress.layers.is_empty() {
+        let gauges_height = app.pull_progress.layers.len().min(MAX_LAYER_GAUGES) as u16 + 2;
+        let parts = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(gauges_height), Constraint::Min(0)].as_ref())
+            .split(inner_area);
+        draw_pull_gauges(f, parts[0], &app.pull_progress);
+        inner_area = parts[1];
+    }
 
     let mut lines_to_display: Vec<Line> = vec![
         Line::from(format!("{}: {}", action_verb, model_name)),
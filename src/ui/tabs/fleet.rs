@@ -0,0 +1,72 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+};
+use chrono::Utc;
+use crate::app::App;
+
+/// Draw the Fleet tab: one row per profile, polled concurrently
+pub fn draw(f: &mut Frame, area: Rect, app: &App) {
+    let block = Block::default().title("Fleet").borders(Borders::ALL);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    let rows: Vec<Row> = app.profiles.iter().enumerate().map(|(i, profile)| {
+        let marker = if i == app.active_profile { "● " } else { "  " };
+        let name = Cell::from(format!("{}{}", marker, profile.name));
+        let host = Cell::from(profile.host.clone());
+        let Some(state) = app.fleet.get(&profile.name) else {
+            return Row::new(vec![name, host, Cell::from("polling..."), Cell::from(""), Cell::from(""), Cell::from(""), Cell::from("")])
+                .style(Style::default().fg(Color::DarkGray));
+        };
+        let (reach, reach_style) = if state.is_reachable() {
+            ("up".to_string(), Style::default().fg(Color::Green))
+        } else {
+            (format!("down: {}", state.error.as_deref().unwrap_or("")), Style::default().fg(Color::Red))
+        };
+        let count = |n: Option<usize>| n.map(|n| n.to_string()).unwrap_or_else(|| "-".into());
+        let summary = state.summary.as_ref();
+        let age = (Utc::now() - state.updated).num_seconds();
+        Row::new(vec![
+            name,
+            host,
+            Cell::from(reach).style(reach_style),
+            Cell::from(count(summary.map(|s| s.workers))),
+            Cell::from(count(summary.map(|s| s.busy))),
+            Cell::from(count(summary.map(|s| s.queued))),
+            Cell::from(format!("{}s ago", age)),
+        ])
+    }).collect();
+
+    let header = Row::new(vec!["Profile", "Host", "Reachable", "Workers", "Busy", "Queued", "Updated"])
+        .style(Style::default().add_modifier(Modifier::BOLD));
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Percentage(18),
+            Constraint::Percentage(22),
+            Constraint::Percentage(24),
+            Constraint::Percentage(9),
+            Constraint::Percentage(9),
+            Constraint::Percentage(9),
+            Constraint::Percentage(9),
+        ],
+    )
+    .header(header)
+    .row_highlight_style(Style::default().fg(Color::Black).bg(Color::LightCyan));
+    let mut state = TableState::default().with_selected(Some(app.fleet_selected));
+    f.render_stateful_widget(table, chunks[0], &mut state);
+
+    f.render_widget(
+        Paragraph::new("UP/DOWN select | Enter open cluster Dashboard | +/- polling interval")
+            .style(Style::default().fg(Color::DarkGray)),
+        chunks[1],
+    );
+}
@@ -1,29 +1,22 @@
 use ratatui::{
-    backend::Backend, Frame,
-    layout::{Constraint, Layout},
-    widgets::{Block, Borders, Table, Row, Cell, Paragraph},
+    Frame,
+    layout::{Constraint, Rect},
+    widgets::{Block, Borders, Table, Row, Cell, Paragraph, Wrap},
 };
-use crate::app::App;
+use crate::app::{App, Resource};
 
 /// Draw the Keys tab
-pub fn draw(f: &mut Frame, app: &App) {
-    let area = f.size();
+pub fn draw(f: &mut Frame, area: Rect, app: &App) {
     let block = Block::default().title("Keys").borders(Borders::ALL);
+    let inner = block.inner(area);
     f.render_widget(block, area);
 
     if let Some(ref keys) = app.auth_keys {
         if keys.is_empty() {
-            let empty = Paragraph::new("No authentication keys found.")
-                .block(Block::default().borders(Borders::ALL).title("Keys"));
-            f.render_widget(empty, area);
+            f.render_widget(Paragraph::new("No authentication keys found."), inner);
             return;
         }
 
-        // split out margins
-        let inner = Layout::default()
-            .constraints([Constraint::Percentage(100)].as_ref())
-            .split(area)[0];
-
         // build rows
         let rows: Vec<Row> = keys.iter().map(|k| {
             Row::new(vec![
@@ -37,15 +30,11 @@ pub fn draw(f: &mut Frame, app: &App) {
         let header = Row::new(vec!["ID", "Name", "Role", "Created At"]);
         let table = Table::new(
             rows,
-            &[Constraint::Percentage(30), Constraint::Percentage(30), Constraint::Percentage(20), Constraint::Percentage(20)]
+            [Constraint::Percentage(30), Constraint::Percentage(30), Constraint::Percentage(20), Constraint::Percentage(20)]
         )
-        .header(header)
-        .block(Block::default().borders(Borders::ALL))
-        .widths([Constraint::Percentage(30), Constraint::Percentage(30), Constraint::Percentage(20), Constraint::Percentage(20)]);
+        .header(header);
         f.render_widget(table, inner);
     } else {
-        let loading = Paragraph::new("Loading keys...")
-            .block(Block::default().borders(Borders::ALL).title("Keys"));
-        f.render_widget(loading, area);
+        f.render_widget(Paragraph::new(app.placeholder(Resource::Keys)).wrap(Wrap { trim: true }), inner);
     }
 }
@@ -1,14 +1,12 @@
 use ratatui::{
-    backend::Backend,
     Frame,
-    layout::{Constraint, Layout},
+    layout::{Constraint, Layout, Rect},
     widgets::{Block, Borders, List, ListItem},
 };
 use crate::app::App;
 
 /// Draw the Logs / Metrics tab
-pub fn draw(f: &mut Frame, app: &App) {
-    let area = f.size();
+pub fn draw(f: &mut Frame, area: Rect, app: &App) {
     let block = Block::default().title("Logs / Metrics").borders(Borders::ALL);
     f.render_widget(block, area);
 
@@ -20,7 +18,10 @@ pub fn draw(f: &mut Frame, app: &App) {
 
     // Build list items from pings and connections
     let mut items: Vec<ListItem> = Vec::new();
+    // Sorted by worker so the list doesn't reshuffle on every poll
     if let Some(pings) = &app.worker_pings {
+        let mut pings: Vec<_> = pings.iter().collect();
+        pings.sort_by_key(|(name, _)| *name);
         for (name, times) in pings {
             if let Some(latest) = times.last() {
                 let line = format!("Ping [{}]: {}", name, latest.to_rfc3339());
@@ -30,6 +31,8 @@ pub fn draw(f: &mut Frame, app: &App) {
     }
     
     if let Some(conns) = &app.worker_connections {
+        let mut conns: Vec<_> = conns.iter().collect();
+        conns.sort_by_key(|(name, _)| *name);
         for (name, cnt) in conns {
             let line = format!("Conns [{}]: {}", name, cnt);
             items.push(ListItem::new(line));
@@ -4,3 +4,6 @@ pub mod queues;
 pub mod keys;
 pub mod console;
 pub mod logs;
+pub mod fleet;
+pub mod alerts;
+pub mod models;
@@ -0,0 +1,91 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState, Wrap},
+};
+use crate::app::{App, Resource};
+
+const COLOR_FULL: Color = Color::Green;
+const COLOR_PARTIAL: Color = Color::Yellow;
+const COLOR_CURSOR_BG: Color = Color::LightCyan;
+/// Width of each worker column
+const WORKER_COL_WIDTH: u16 = 10;
+/// Width of the Model, Replicas and Queued columns with the spacing after each
+const FIXED_COLS_WIDTH: u16 = 20 + 9 + 7 + 3;
+
+/// Draw the Models tab: a models × workers inventory matrix
+pub fn draw(f: &mut Frame, area: Rect, app: &App) {
+    let block = Block::default().title("Models").borders(Borders::ALL);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+    let hint = "arrows/wasd move | Enter pull (missing) or delete (present) on that worker | ● present  · missing";
+
+    let Some(tags) = &app.worker_tags else {
+        f.render_widget(Paragraph::new(hint).style(Style::default().fg(Color::DarkGray)), chunks[1]);
+        f.render_widget(Paragraph::new(app.placeholder(Resource::Tags)).wrap(Wrap { trim: true }), chunks[0]);
+        return;
+    };
+    let models = app.known_models();
+    let workers = app.worker_names();
+    if models.is_empty() || workers.is_empty() {
+        f.render_widget(Paragraph::new(hint).style(Style::default().fg(Color::DarkGray)), chunks[1]);
+        f.render_widget(Paragraph::new("No models reported by any worker."), chunks[0]);
+        return;
+    }
+    let (cursor_row, cursor_col) = app.models_cursor;
+
+    // Only as many worker columns as fit, shifted along to keep the cursor in view
+    let fitting = (chunks[0].width.saturating_sub(FIXED_COLS_WIDTH) / (WORKER_COL_WIDTH + 1)).max(1) as usize;
+    let shown = fitting.min(workers.len());
+    let first = (cursor_col + 1).saturating_sub(shown).min(workers.len() - shown);
+    let columns = first..first + shown;
+    let hint = if shown < workers.len() {
+        format!("workers {}-{} of {} | {}", first + 1, first + shown, workers.len(), hint)
+    } else {
+        hint.to_string()
+    };
+    f.render_widget(Paragraph::new(hint).style(Style::default().fg(Color::DarkGray)), chunks[1]);
+
+    let rows: Vec<Row> = models.iter().enumerate().map(|(r, model)| {
+        let present: Vec<bool> = workers.iter()
+            .map(|w| tags.get(w).is_some_and(|m| m.contains(model)))
+            .collect();
+        let replicas = present.iter().filter(|p| **p).count();
+        let color = if replicas == workers.len() { COLOR_FULL } else { COLOR_PARTIAL };
+        let queued = app.queue_map.as_ref()
+            .and_then(|q| q.get(&format!("Model: {}", model)).or_else(|| q.get(model)))
+            .copied()
+            .unwrap_or(0);
+
+        let mut cells = vec![
+            Cell::from(model.clone()).style(Style::default().fg(color)),
+            Cell::from(format!("{}/{}", replicas, workers.len())).style(Style::default().fg(color)),
+            Cell::from(queued.to_string()),
+        ];
+        cells.extend(present.iter().enumerate().skip(first).take(shown).map(|(c, here)| {
+            let mut style = Style::default().fg(if *here { COLOR_FULL } else { Color::DarkGray });
+            if (r, c) == (cursor_row, cursor_col) {
+                style = style.bg(COLOR_CURSOR_BG).fg(Color::Black);
+            }
+            Cell::from(if *here { "●" } else { "·" }).style(style)
+        }));
+        Row::new(cells)
+    }).collect();
+
+    let mut header = vec!["Model".to_string(), "Replicas".to_string(), "Queued".to_string()];
+    header.extend(workers[columns.clone()].iter().map(|w| w.chars().take(WORKER_COL_WIDTH as usize - 1).collect()));
+    let mut widths = vec![Constraint::Min(20), Constraint::Length(9), Constraint::Length(7)];
+    widths.extend(columns.map(|_| Constraint::Length(WORKER_COL_WIDTH)));
+
+    let table = Table::new(rows, widths)
+        .header(Row::new(header).style(Style::default().add_modifier(Modifier::BOLD)));
+    // The selected row scrolls the table; the cursor cell carries the highlight
+    let mut state = TableState::default().with_selected(Some(cursor_row.min(models.len() - 1)));
+    f.render_stateful_widget(table, chunks[0], &mut state);
+}
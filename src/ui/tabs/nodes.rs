@@ -1,14 +1,31 @@
 use ratatui::{
-    backend::Backend, Frame,
-    layout::{Constraint, Direction, Layout},
-    widgets::{Block, Borders, Table, Row, Cell, Paragraph},
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Table, Row, Cell, Paragraph, Wrap},
 };
-use crate::app::App;
+use crate::app::{App, Resource};
+use crate::models::StatusBreakdown;
+
+/// Width of the worker name column in the timeline
+const NAME_WIDTH: u16 = 16;
+/// Width of the breakdown column in the timeline
+const BREAKDOWN_WIDTH: u16 = 40;
+
+/// Colour of a worker status in tables and timelines
+fn status_color(status: &str) -> Color {
+    match status {
+        "Polling" => Color::Green,
+        "Working" => Color::Red,
+        _ => Color::Yellow,
+    }
+}
 
 /// Draw the Nodes tab
-pub fn draw(f: &mut Frame, app: &App) {
-    let area = f.size();
+pub fn draw(f: &mut Frame, area: Rect, app: &App) {
     let block = Block::default().title("Nodes").borders(Borders::ALL);
+    let inner = block.inner(area);
     f.render_widget(block, area);
 
     // If we have fetched statuses and connections
@@ -18,17 +35,27 @@ pub fn draw(f: &mut Frame, app: &App) {
         &app.worker_pings,
         &app.worker_versions,
     ) {
-        // Split area: full width table
-        let inner = Layout::default()
+        let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Percentage(100)].as_ref())
-            .split(area)[0];
+            .constraints([Constraint::Percentage(45), Constraint::Min(0)])
+            .split(inner);
+        let strip_width = chunks[1].width.saturating_sub(NAME_WIDTH + BREAKDOWN_WIDTH + 2).max(1) as usize;
+        let now = app.now();
 
         // Build rows: one per worker
-        let mut workers: Vec<_> = statuses.keys().collect();
+        let mut workers: Vec<_> = statuses.keys().filter(|n| *n != "Unauthenticated").collect();
         workers.sort();
-        let rows: Vec<Row> = workers.iter().map(|name| {
-            let status = statuses.get(*name).map(|s| format!("{:?}", s)).unwrap_or_default();
+        let breakdowns: Vec<StatusBreakdown> = workers.iter().map(|name| {
+            StatusBreakdown::from_statuses(
+                &statuses[*name],
+                pings.get(*name).map(Vec::as_slice),
+                strip_width,
+                now,
+            )
+        }).collect();
+
+        let rows: Vec<Row> = workers.iter().zip(&breakdowns).map(|(name, breakdown)| {
+            let status = statuses.get(*name).and_then(|s| s.last()).cloned().unwrap_or_else(|| "Unknown".into());
             let conn = conns.get(*name).unwrap_or(&0).to_string();
             let ping = pings.get(*name)
                 .and_then(|times| times.last())
@@ -39,27 +66,73 @@ pub fn draw(f: &mut Frame, app: &App) {
                 .unwrap_or_else(|| "-".into());
             Row::new(vec![
                 Cell::from(name.as_str()),
-                Cell::from(status),
+                Cell::from(status.clone()).style(Style::default().fg(status_color(&status))),
                 Cell::from(conn),
+                Cell::from(format!("{:.0}%", breakdown.utilisation * 100.0)),
                 Cell::from(ping),
                 Cell::from(vers),
             ])
         }).collect();
 
         // Render table
-        let header = Row::new(vec!["Name", "Status", "Conns", "Last Ping", "Versions"]);
+        let header = Row::new(vec!["Name", "Status", "Conns", "Util", "Last Ping", "Versions"]);
         let table = Table::new(
             rows,
-            &[Constraint::Percentage(20), Constraint::Percentage(15), Constraint::Percentage(15), Constraint::Percentage(25), Constraint::Percentage(25)]
+            [Constraint::Percentage(20), Constraint::Percentage(12), Constraint::Percentage(8), Constraint::Percentage(8), Constraint::Percentage(27), Constraint::Percentage(25)]
         )
-        .header(header)
-        .block(Block::default().borders(Borders::ALL))
-        .widths([Constraint::Percentage(20), Constraint::Percentage(15), Constraint::Percentage(15), Constraint::Percentage(25), Constraint::Percentage(25)]);
-        f.render_widget(table, inner);
+        .header(header);
+        f.render_widget(table, chunks[0]);
+
+        draw_timelines(f, chunks[1], app, &workers, &breakdowns, strip_width);
     } else {
-        // Loading or error state
-        let loading = Paragraph::new("Loading nodes...")
-            .block(Block::default().borders(Borders::ALL).title("Nodes"));
-        f.render_widget(loading, area);
+        // Loading or error state of the first data set still missing
+        let missing = [
+            (app.worker_statuses.is_none(), Resource::Statuses),
+            (app.worker_connections.is_none(), Resource::Connections),
+            (app.worker_pings.is_none(), Resource::Pings),
+            (app.worker_versions.is_none(), Resource::Versions),
+        ]
+        .into_iter()
+        .find_map(|(missing, resource)| missing.then_some(resource))
+        .unwrap_or(Resource::Statuses);
+        f.render_widget(Paragraph::new(app.placeholder(missing)).wrap(Wrap { trim: true }), inner);
     }
-}
\ No newline at end of file
+}
+
+/// One coloured strip per worker over its status vector, newest on the right
+fn draw_timelines(f: &mut Frame, area: Rect, app: &App, workers: &[&String], breakdowns: &[StatusBreakdown], strip_width: usize) {
+    let Some(statuses) = &app.worker_statuses else { return };
+    let legend = Line::from(vec![
+        Span::raw("Status timeline  "),
+        Span::styled("■ Polling ", Style::default().fg(status_color("Polling"))),
+        Span::styled("■ Working ", Style::default().fg(status_color("Working"))),
+        Span::styled("■ other", Style::default().fg(status_color(""))),
+    ]);
+
+    let lines: Vec<Line> = workers.iter().zip(breakdowns).map(|(name, breakdown)| {
+        let history = &statuses[*name];
+        let visible = &history[history.len().saturating_sub(strip_width)..];
+
+        let mut spans = vec![Span::raw(format!("{:<w$.w$} ", name, w = NAME_WIDTH as usize - 1))];
+        spans.push(Span::raw(" ".repeat(strip_width - visible.len())));
+        spans.extend(visible.iter().map(|s| Span::styled("▮", Style::default().fg(status_color(s)))));
+
+        let total: usize = breakdown.shares.iter().map(|s| s.samples).sum();
+        let summary: Vec<String> = breakdown.shares.iter().map(|share| {
+            match share.seconds {
+                Some(secs) => format!("{} {:.0}s", share.state, secs),
+                None => format!("{} {:.0}%", share.state, share.samples as f64 * 100.0 / total as f64),
+            }
+        }).collect();
+        spans.push(Span::raw(format!(
+            "  {:>3.0}% busy  {}",
+            breakdown.utilisation * 100.0,
+            summary.join(", "),
+        )));
+        Line::from(spans)
+    }).collect();
+
+    let paragraph = Paragraph::new(lines)
+        .block(Block::default().title(legend).borders(Borders::ALL));
+    f.render_widget(paragraph, area);
+}
@@ -1,13 +1,21 @@
 use ratatui::{
-    backend::Backend, Frame,
-    layout::{Constraint, Direction, Layout},
-    widgets::{Block, Borders, Table, Row, Cell, Paragraph},
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    symbols,
+    text::Span,
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Table, Row, Cell, Paragraph, Wrap},
 };
-use crate::app::App;
+use crate::app::{App, Resource};
 
-pub fn draw(f: &mut Frame, app: &App) {
-    let area = f.size();
+/// Time window shown by the queue trend chart
+const TREND_WINDOW_SECS: f64 = 600.0;
+/// Colors of the charted queues, busiest first
+const SERIES_COLORS: [Color; 6] = [Color::Yellow, Color::Cyan, Color::Green, Color::Magenta, Color::Red, Color::Blue];
+
+pub fn draw(f: &mut Frame, area: Rect, app: &App) {
     let block = Block::default().title("Queues").borders(Borders::ALL);
+    let inner = block.inner(area);
     f.render_widget(block, area);
 
     if let Some(ref queue_map) = app.queue_map {
@@ -15,7 +23,7 @@ pub fn draw(f: &mut Frame, app: &App) {
         let cols = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
-            .split(area);
+            .split(inner);
 
         // for now we just show a unified list on the left
         let mut items: Vec<(&String, &usize)> = queue_map.iter().collect();
@@ -26,18 +34,60 @@ pub fn draw(f: &mut Frame, app: &App) {
             .collect();
 
         let table = Table::new(
-                rows, 
-                &[Constraint::Percentage(70), Constraint::Percentage(30)]
+                rows,
+                [Constraint::Percentage(70), Constraint::Percentage(30)]
             )
             .header(Row::new(vec![Cell::from("Queue"), Cell::from("Count")]))
-            .block(Block::default().borders(Borders::ALL))
-            .widths([Constraint::Percentage(70), Constraint::Percentage(30)]);
+            .block(Block::default().borders(Borders::ALL));
         f.render_widget(table, cols[0]);
 
-        // TODO: render node-specific queues in cols[1]
+        draw_trend_chart(f, cols[1], app);
     } else {
-        let loading = Paragraph::new("Loading queues…")
-            .block(Block::default().title("Queues").borders(Borders::ALL));
-        f.render_widget(loading, area);
+        f.render_widget(Paragraph::new(app.placeholder(Resource::Queue)).wrap(Wrap { trim: true }), inner);
+    }
+}
+
+/// Queue lengths over the last `TREND_WINDOW_SECS`, one line per model queue
+fn draw_trend_chart(f: &mut Frame, area: Rect, app: &App) {
+    let now = app.now();
+    // Points as (seconds relative to now, length), only inside the window
+    let mut series: Vec<(String, Vec<(f64, f64)>)> = app.history.queues.iter()
+        .filter(|(name, _)| !name.starts_with("Node:"))
+        .map(|(name, samples)| {
+            let points: Vec<(f64, f64)> = samples.iter()
+                .map(|s| ((s.at - now).num_milliseconds() as f64 / 1000.0, s.value as f64))
+                .filter(|(x, _)| *x >= -TREND_WINDOW_SECS)
+                .collect();
+            (name.trim_start_matches("Model:").trim().to_string(), points)
+        })
+        .filter(|(_, points)| !points.is_empty())
+        .collect();
+    let peak = |points: &[(f64, f64)]| points.iter().map(|p| p.1).fold(0.0, f64::max);
+    series.sort_by(|a, b| peak(&b.1).total_cmp(&peak(&a.1)).then_with(|| a.0.cmp(&b.0)));
+    series.truncate(SERIES_COLORS.len());
+
+    let y_max = series.iter().map(|(_, p)| peak(p)).fold(1.0, f64::max).ceil();
+    let datasets: Vec<Dataset> = series.iter().zip(SERIES_COLORS).map(|((name, points), color)| {
+        Dataset::default()
+            .name(name.as_str())
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(color))
+            .data(points)
+    }).collect();
+
+    let block = Block::default().title("Queue trend (last 10 min)").borders(Borders::ALL);
+    if datasets.is_empty() {
+        f.render_widget(Paragraph::new("No queue history yet").block(block), area);
+        return;
     }
+    let chart = Chart::new(datasets)
+        .block(block)
+        .x_axis(Axis::default()
+            .bounds([-TREND_WINDOW_SECS, 0.0])
+            .labels(vec![Span::raw("-10m"), Span::raw("-5m"), Span::raw("now")]))
+        .y_axis(Axis::default()
+            .bounds([0.0, y_max])
+            .labels(vec![Span::raw("0"), Span::raw(format!("{}", y_max))]));
+    f.render_widget(chart, area);
 }
@@ -1,8 +1,13 @@
 use std::io;
 use crossterm::{execute, terminal::{EnterAlternateScreen, LeaveAlternateScreen, enable_raw_mode, disable_raw_mode}};
 use ratatui::{backend::CrosstermBackend, Terminal, Frame};
-use ratatui::layout::{Rect};
-use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Paragraph, Tabs};
+use chrono::{DateTime, Local, Utc};
+use crate::app::{App, ConnectionStatus, Tab};
+use crate::recording::Replay;
 
 /// Set up the terminal in raw mode and enter the alternate screen
 pub fn setup_terminal() -> io::Result<Terminal<CrosstermBackend<io::Stdout>>> {
@@ -23,13 +28,116 @@ pub fn restore_terminal() -> io::Result<()> {
     Ok(())
 }
 
-/// Draw banner messages at the top of the frame
+/// Columns the profile label may take when the tabs don't all fit beside it
+const NARROW_LABEL_WIDTH: usize = 24;
+
+/// Draw the tab strip, highlighting the active tab
+pub fn draw_tab_bar(f: &mut Frame, area: Rect, app: &App) {
+    let selected = Tab::all()
+        .iter()
+        .position(|t| *t == app.current_tab)
+        .unwrap_or(0);
+    let mut titles: Vec<String> = Tab::all()
+        .iter()
+        .enumerate()
+        .map(|(i, tab)| format!("{} {}", i + 1, tab.title()))
+        .collect();
+    // Each title is padded by a space on both sides, with a divider between
+    let width_of = |titles: &[String]| titles.iter().map(|t| t.chars().count() + 3).sum::<usize>().saturating_sub(1);
+    // When the tabs don't all fit beside a narrow profile label, only the
+    // active one keeps its name so it stays in view
+    if width_of(&titles) + NARROW_LABEL_WIDTH > area.width as usize {
+        for (i, title) in titles.iter_mut().enumerate() {
+            if i != selected {
+                *title = (i + 1).to_string();
+            }
+        }
+    }
+    let tabs_width = width_of(&titles);
+    let titles: Vec<Line> = titles.into_iter().map(Line::from).collect();
+    let tabs = Tabs::new(titles)
+        .select(selected)
+        .style(Style::default().fg(Color::Gray))
+        .highlight_style(Style::default().fg(Color::Black).bg(Color::LightCyan).add_modifier(Modifier::BOLD))
+        .divider("|");
+
+    // Active profile and its connection on the right, so they are visible
+    // from every tab; shortened to leave the tabs their room
+    let profile = app.profiles.get(app.active_profile).map(|p| p.name.as_str()).unwrap_or("-");
+    let (status, short, color) = match &app.replay {
+        Some(replay) => replay_label(replay),
+        None => connection_label(&app.connection_status()),
+    };
+    let budget = (area.width as usize).saturating_sub(tabs_width).max(NARROW_LABEL_WIDTH);
+    let label = [format!(" {} {} (F4) ", profile, status), format!(" {} {} ", profile, short)]
+        .into_iter()
+        .find(|label| label.chars().count() <= budget)
+        .unwrap_or_else(|| format!(" {} ", short));
+    let width = label.chars().count().min(budget).min(area.width as usize) as u16;
+    let parts = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(0), Constraint::Length(width)])
+        .split(area);
+    f.render_widget(tabs, parts[0]);
+    f.render_widget(
+        Paragraph::new(label).style(Style::default().fg(Color::Black).bg(color)),
+        parts[1],
+    );
+}
+
+/// Text, short text for narrow bars and background colour of the
+/// connection indicator
+fn connection_label(status: &ConnectionStatus) -> (String, String, Color) {
+    match status {
+        ConnectionStatus::Connecting => ("connecting…".to_string(), "…".to_string(), Color::Gray),
+        ConnectionStatus::Online => ("online".to_string(), "online".to_string(), Color::Green),
+        ConnectionStatus::Degraded(failing) if failing.len() <= 2 => {
+            let names: Vec<&str> = failing.iter().map(|r| r.label()).collect();
+            (format!("degraded: {} failing", names.join(", ")), format!("{} failing", failing.len()), Color::Yellow)
+        }
+        ConnectionStatus::Degraded(failing) => (
+            format!("degraded: {} endpoints failing", failing.len()),
+            format!("{} failing", failing.len()),
+            Color::Yellow,
+        ),
+        ConnectionStatus::Failing(failing) => (
+            format!("error: {} endpoints failing", failing.len()),
+            format!("{} failing", failing.len()),
+            Color::Red,
+        ),
+        ConnectionStatus::Offline(since) => (
+            format!("unreachable since {}", since.with_timezone(&chrono::Local).format("%H:%M:%S")),
+            "offline".to_string(),
+            Color::Red,
+        ),
+    }
+}
+
+/// Text, short text for narrow bars and background colour of the replay
+/// clock in place of the connection indicator
+fn replay_label(replay: &Replay) -> (String, String, Color) {
+    let time = |at: DateTime<Utc>| at.with_timezone(&Local).format("%H:%M:%S").to_string();
+    let state = if replay.playing { "playing" } else { "paused" };
+    let short = if replay.playing {
+        format!("{}x {}", replay.speed(), time(replay.position))
+    } else {
+        format!("paused {}", time(replay.position))
+    };
+    (
+        format!("replay {} {}x {} / {}", state, replay.speed(), time(replay.position), time(replay.end())),
+        short,
+        Color::LightBlue,
+    )
+}
+
+/// Draw banner messages at the bottom of the frame
 pub fn draw_banners(f: &mut Frame, banners: &[String]) {
     if banners.is_empty() {
         return;
     }
-    // Reserve the first line for banners
-    let area = Rect { x: 0, y: 0, width: f.area().width, height: banners.len() as u16 };
+    // Reserve the last lines for banners so the tab bar stays visible
+    let height = (banners.len() as u16).min(f.area().height);
+    let area = Rect { x: 0, y: f.area().height - height, width: f.area().width, height };
     let text = banners.join(" | ");
     let para = Paragraph::new(text)
         .block(Block::default().borders(Borders::NONE));
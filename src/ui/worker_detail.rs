@@ -0,0 +1,179 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Sparkline},
+};
+use chrono::{DateTime, Local, Utc};
+use crate::app::App;
+use crate::models::PingStats;
+
+const COLOR_BORDER: Color = Color::Cyan;
+const COLOR_HINT: Color = Color::DarkGray;
+/// A worker is never flagged stale sooner than this
+const STALE_MIN_SECS: f64 = 30.0;
+
+/// How overdue a worker's next ping is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Freshness {
+    Fresh,
+    Late,
+    Stale,
+}
+
+impl Freshness {
+    /// Late after 1.5 mean intervals, stale after 3 (and at least `STALE_MIN_SECS`)
+    pub fn classify(since_last: f64, stats: Option<&PingStats>) -> Self {
+        let mean = stats.map(|s| s.mean).unwrap_or(STALE_MIN_SECS / 3.0);
+        if since_last > (mean * 3.0).max(STALE_MIN_SECS) {
+            Freshness::Stale
+        } else if since_last > mean * 1.5 {
+            Freshness::Late
+        } else {
+            Freshness::Fresh
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            Freshness::Fresh => Color::Green,
+            Freshness::Late => Color::Yellow,
+            Freshness::Stale => Color::Red,
+        }
+    }
+}
+
+/// Draw the detail screen of `app.worker_detail` over the Dashboard
+pub fn draw(f: &mut Frame, area: Rect, app: &App) {
+    let Some(name) = &app.worker_detail else { return };
+    let block = Block::default()
+        .title(Span::styled(format!("Worker: {}", name), Style::default().fg(COLOR_BORDER)))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(COLOR_BORDER));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(7), Constraint::Length(6), Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    f.render_widget(
+        Paragraph::new("ESC back | UP/DOWN scroll pings").style(Style::default().fg(COLOR_HINT)),
+        chunks[3],
+    );
+
+    let Some(times) = app.worker_pings.as_ref().and_then(|p| p.get(name)) else {
+        f.render_widget(Paragraph::new("No ping data for worker"), chunks[0]);
+        return;
+    };
+    let mut times: Vec<DateTime<Utc>> = times.clone();
+    times.sort();
+    let stats = PingStats::from_pings(&times);
+    let now = app.now();
+
+    // Summary with the live "seconds ago" counter
+    let mut lines: Vec<Line> = Vec::new();
+    match times.last() {
+        Some(last) => {
+            let since = (now - *last).num_milliseconds() as f64 / 1000.0;
+            let freshness = Freshness::classify(since, stats.as_ref());
+            let label = match freshness {
+                Freshness::Fresh => "",
+                Freshness::Late => "  (late)",
+                Freshness::Stale => "  (STALE)",
+            };
+            lines.push(Line::from(vec![
+                Span::styled("Last ping: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    format!("{:.1}s ago{}", since, label),
+                    Style::default().fg(freshness.color()).add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!("  at {}", last.with_timezone(&Local).format("%H:%M:%S"))),
+            ]));
+        }
+        None => lines.push(Line::from("No pings recorded")),
+    }
+    lines.push(Line::from(format!("Pings recorded: {}", times.len())));
+    if let Some(s) = &stats {
+        lines.push(Line::from(format!("Interval mean: {:.2}s   min: {:.2}s   max: {:.2}s", s.mean, s.min, s.max)));
+        let relative = if s.mean > 0.0 { s.jitter / s.mean * 100.0 } else { 0.0 };
+        lines.push(Line::from(format!("Jitter (std dev): {:.2}s ({:.0}% of mean)", s.jitter, relative)));
+    } else {
+        lines.push(Line::from("Not enough pings for interval statistics"));
+    }
+    if let Some(status) = app.worker_statuses.as_ref().and_then(|s| s.get(name)).and_then(|s| s.last()) {
+        lines.push(Line::from(format!("Status: {}", status)));
+    }
+    f.render_widget(Paragraph::new(lines), chunks[0]);
+
+    // Interval timeline, one bar per gap between pings (in tenths of a second)
+    if let Some(s) = &stats {
+        let width = chunks[1].width.saturating_sub(2) as usize;
+        let bars: Vec<u64> = s.intervals.iter()
+            .skip(s.intervals.len().saturating_sub(width))
+            .map(|i| (i * 10.0).round() as u64)
+            .collect();
+        let sparkline = Sparkline::default()
+            .block(Block::default().title("Intervals between pings").borders(Borders::ALL))
+            .data(&bars)
+            .style(Style::default().fg(Color::Cyan));
+        f.render_widget(sparkline, chunks[1]);
+    }
+
+    // Full ping timeline, newest first, with the gap to the previous ping
+    let rows: Vec<Line> = times.iter().enumerate().rev().map(|(i, t)| {
+        let gap = (i > 0).then(|| (*t - times[i - 1]).num_milliseconds() as f64 / 1000.0);
+        let style = match (gap, &stats) {
+            (Some(gap), Some(s)) if gap > (s.mean * 3.0).max(STALE_MIN_SECS) => Style::default().fg(Color::Red),
+            (Some(gap), Some(s)) if gap > s.mean * 1.5 => Style::default().fg(Color::Yellow),
+            _ => Style::default(),
+        };
+        Line::from(Span::styled(
+            format!(
+                "{}  {}",
+                t.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S%.3f"),
+                gap.map(|g| format!("+{:.2}s", g)).unwrap_or_else(|| "first".into()),
+            ),
+            style,
+        ))
+    }).collect();
+    let list = Paragraph::new(rows)
+        .block(Block::default().title("Ping timeline (newest first)").borders(Borders::ALL))
+        .scroll((app.worker_detail_scroll, 0));
+    f.render_widget(list, chunks[2]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(mean: f64) -> PingStats {
+        PingStats { intervals: vec![mean], mean, min: mean, max: mean, jitter: 0.0 }
+    }
+
+    #[test]
+    fn pings_turn_late_then_stale_relative_to_the_mean() {
+        let stats = stats(20.0);
+        assert_eq!(Freshness::classify(30.0, Some(&stats)), Freshness::Fresh);
+        assert_eq!(Freshness::classify(31.0, Some(&stats)), Freshness::Late);
+        assert_eq!(Freshness::classify(60.0, Some(&stats)), Freshness::Late);
+        assert_eq!(Freshness::classify(61.0, Some(&stats)), Freshness::Stale);
+    }
+
+    #[test]
+    fn fast_pings_are_not_stale_before_the_minimum() {
+        let stats = stats(2.0);
+        assert_eq!(Freshness::classify(4.0, Some(&stats)), Freshness::Late);
+        assert_eq!(Freshness::classify(STALE_MIN_SECS, Some(&stats)), Freshness::Late);
+        assert_eq!(Freshness::classify(STALE_MIN_SECS + 1.0, Some(&stats)), Freshness::Stale);
+    }
+
+    #[test]
+    fn a_single_ping_is_judged_by_the_minimum() {
+        assert_eq!(Freshness::classify(15.0, None), Freshness::Fresh);
+        assert_eq!(Freshness::classify(16.0, None), Freshness::Late);
+        assert_eq!(Freshness::classify(STALE_MIN_SECS + 1.0, None), Freshness::Stale);
+    }
+}
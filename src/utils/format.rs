@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+/// Byte count with a binary unit, e.g. `1.5 GiB`
+pub fn bytes(n: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = n as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", n)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// Remaining time as `m:ss`, or `h:mm:ss` past an hour
+pub fn eta(d: Duration) -> String {
+    let secs = d.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_picks_a_binary_unit() {
+        assert_eq!(bytes(0), "0 B");
+        assert_eq!(bytes(1023), "1023 B");
+        assert_eq!(bytes(1024), "1.0 KiB");
+        assert_eq!(bytes(1536 * 1024 * 1024), "1.5 GiB");
+        assert_eq!(bytes(u64::MAX), "16777216.0 TiB");
+    }
+
+    #[test]
+    fn eta_shows_hours_only_when_needed() {
+        assert_eq!(eta(Duration::from_secs(0)), "0:00");
+        assert_eq!(eta(Duration::from_secs(59)), "0:59");
+        assert_eq!(eta(Duration::from_secs(3599)), "59:59");
+        assert_eq!(eta(Duration::from_secs(3600 + 62)), "1:01:02");
+        assert_eq!(eta(Duration::from_millis(1999)), "0:01");
+    }
+}
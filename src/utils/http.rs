@@ -1,12 +1,16 @@
-use reqwest::{header::{HeaderMap, HeaderValue, AUTHORIZATION}, Client, Response};
-use std::time::Duration;
+use reqwest::{header::{HeaderMap, HeaderValue, AUTHORIZATION}, Client, RequestBuilder, Response};
 use crate::errors::ClientError;
+use crate::utils::retry::{CircuitBreakers, RequestPolicy};
 
 /// A simple HTTP client wrapper for HiveCore endpoints
 pub struct HttpClient {
     client: Client,
     pub base_url: String,
     pub headers: HeaderMap,
+    /// How errors refer to the token when HiveCore rejects it
+    token_name: &'static str,
+    policy: RequestPolicy,
+    breakers: CircuitBreakers,
 }
 
 impl HttpClient {
@@ -22,17 +26,58 @@ impl HttpClient {
         auth_value.set_sensitive(true);
         headers.insert(AUTHORIZATION, auth_value);
 
+        let policy = RequestPolicy::current();
         let client = Client::builder()
-            .timeout(Duration::from_secs(30))
+            .timeout(policy.timeout)
             .build()?;
 
         Ok(HttpClient {
             client,
             base_url: base_url.into(),
             headers,
+            token_name: "token",
+            policy,
+            breakers: CircuitBreakers::default(),
         })
     }
 
+    /// Name the token in authentication errors, e.g. "admin token"
+    pub fn with_token_name(mut self, name: &'static str) -> Self {
+        self.token_name = name;
+        self
+    }
+
+    /// Send `request` to `path` through its circuit breaker, retrying
+    /// transient failures with backoff when `idempotent`
+    async fn send(&self, request: RequestBuilder, path: &str, idempotent: bool) -> Result<Response, ClientError> {
+        let endpoint = path.split('?').next().unwrap_or(path).trim_start_matches('/');
+        self.breakers.check(endpoint)?;
+
+        let attempts = if idempotent { self.policy.retries + 1 } else { 1 };
+        for retry in 1..attempts {
+            let Some(this_try) = request.try_clone() else { break };
+            match self.send_once(this_try).await {
+                Err(e) if e.is_transient() => tokio::time::sleep(self.policy.backoff(retry)).await,
+                other => return self.breakers.record(endpoint, other),
+            }
+        }
+        let result = self.send_once(request).await;
+        self.breakers.record(endpoint, result)
+    }
+
+    /// Send `request` once, turning non-success statuses into typed errors
+    /// that keep the response body
+    async fn send_once(&self, request: RequestBuilder) -> Result<Response, ClientError> {
+        let res = request.send().await?;
+        let status = res.status();
+        if status.is_success() {
+            return Ok(res);
+        }
+        let url = res.url().to_string();
+        let body = res.text().await.unwrap_or_default();
+        Err(ClientError::from_status(status.as_u16(), &url, self.token_name, body))
+    }
+
     /// Perform a GET request and deserialize JSON response
     pub async fn get<T: serde::de::DeserializeOwned>(
         &self, 
@@ -46,12 +91,10 @@ impl HttpClient {
             used_headers = headers.unwrap();
         }
         
-        let res = self.client
+        let request = self.client
             .get(&url)
-            .headers(used_headers)
-            .send()
-            .await?
-            .error_for_status()?;
+            .headers(used_headers);
+        let res = self.send(request, path, true).await?;
         let data = res.json::<T>().await?;
         Ok(data)
     }
@@ -70,13 +113,11 @@ impl HttpClient {
             used_headers = headers.unwrap();
         }
         
-        let res = self.client
+        let request = self.client
             .post(&url)
             .headers(used_headers)
-            .json(body)
-            .send()
-            .await?
-            .error_for_status()?;
+            .json(body);
+        let res = self.send(request, path, false).await?;
         let data = res.json::<T>().await?;
         Ok(data)
     }
@@ -94,13 +135,11 @@ impl HttpClient {
             used_headers = headers.unwrap();
         }
         
-        let res = self.client
+        let request = self.client
             .post(&url)
             .headers(used_headers)
-            .json(body)
-            .send()
-            .await?
-            .error_for_status()?;
+            .json(body);
+        let res = self.send(request, path, false).await?;
         Ok(res)
     }
 
@@ -117,13 +156,11 @@ impl HttpClient {
             used_headers = headers.unwrap();
         }
 
-        let res = self.client
+        let request = self.client
             .delete(&url)
             .headers(used_headers)
-            .json(body)
-            .send()
-            .await?
-            .error_for_status()?;
+            .json(body);
+        let res = self.send(request, path, false).await?;
         let data = res.json::<T>().await?;
         Ok(data)
     }
@@ -141,13 +178,11 @@ impl HttpClient {
             used_headers = headers.unwrap();
         }
 
-        let res = self.client
+        let request = self.client
             .delete(&url)
             .headers(used_headers)
-            .json(body)
-            .send()
-            .await?
-            .error_for_status()?;
+            .json(body);
+        let res = self.send(request, path, false).await?;
         Ok(res)
     }
 }
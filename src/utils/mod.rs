@@ -1,3 +1,7 @@
 pub mod clipboard;
 pub mod http;
-pub mod parsing;
\ No newline at end of file
+pub mod parsing;
+pub mod server;
+pub mod format;
+pub mod ndjson;
+pub mod retry;
@@ -0,0 +1,171 @@
+use futures::{Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::errors::ClientError;
+
+/// Split a byte stream into newline-delimited lines.
+///
+/// Chunks may end anywhere, including inside a multibyte character; bytes
+/// are buffered until a full line is available. Blank lines are skipped and
+/// a last line without a trailing newline is still emitted. A line that is
+/// not valid UTF-8 yields `ClientError::Decode` and the stream carries on;
+/// a transport error is yielded once and ends the stream.
+pub fn lines<S, B, E>(bytes: S) -> impl Stream<Item = Result<String, ClientError>>
+where
+    S: Stream<Item = Result<B, E>>,
+    B: AsRef<[u8]>,
+    E: Into<ClientError>,
+{
+    let state = LineState { bytes: Box::pin(bytes), buffer: Vec::new(), done: false };
+    futures::stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(line) = state.next_line() {
+                return Some((line, state));
+            }
+            if state.done {
+                return None;
+            }
+            match state.bytes.next().await {
+                Some(Ok(chunk)) => state.buffer.extend_from_slice(chunk.as_ref()),
+                Some(Err(e)) => {
+                    state.done = true;
+                    state.buffer.clear();
+                    return Some((Err(e.into()), state));
+                }
+                None => {
+                    state.done = true;
+                    state.buffer.push(b'\n');
+                }
+            }
+        }
+    })
+}
+
+struct LineState<S> {
+    bytes: std::pin::Pin<Box<S>>,
+    buffer: Vec<u8>,
+    done: bool,
+}
+
+impl<S> LineState<S> {
+    /// Take the next complete, non-blank line out of the buffer
+    fn next_line(&mut self) -> Option<Result<String, ClientError>> {
+        while let Some(pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=pos).collect();
+            match String::from_utf8(line) {
+                Ok(line) if line.trim().is_empty() => continue,
+                Ok(line) => return Some(Ok(line.trim().to_string())),
+                Err(e) => return Some(Err(ClientError::Decode(e))),
+            }
+        }
+        None
+    }
+}
+
+/// Parse one line into `T`.
+///
+/// A line carrying a non-null, non-false `error` field is reported as
+/// `ClientError::Stream` with the server's message instead.
+pub fn decode<T: DeserializeOwned>(line: &str) -> Result<T, ClientError> {
+    let value: Value = serde_json::from_str(line)?;
+    if let Some(error) = value.get("error").filter(|e| !e.is_null() && e.as_bool() != Some(false)) {
+        let message = error.as_str().map(str::to_string).unwrap_or_else(|| error.to_string());
+        return Err(ClientError::Stream(message));
+    }
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Typed events of an NDJSON response body, one per line
+pub fn events<T: DeserializeOwned>(resp: reqwest::Response) -> impl Stream<Item = Result<T, ClientError>> {
+    lines(resp.bytes_stream()).map(|line| line.and_then(|line| decode(&line)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PullStatus;
+
+    /// Collect the lines of a body delivered as `chunks`
+    async fn collect(chunks: Vec<&[u8]>) -> Vec<Result<String, ClientError>> {
+        let chunks: Vec<Result<Vec<u8>, std::io::Error>> = chunks.into_iter().map(|c| Ok(c.to_vec())).collect();
+        lines(futures::stream::iter(chunks)).collect().await
+    }
+
+    fn ok(lines: Vec<Result<String, ClientError>>) -> Vec<String> {
+        lines.into_iter().map(|l| l.unwrap()).collect()
+    }
+
+    #[tokio::test]
+    async fn joins_lines_split_across_chunks() {
+        let out = collect(vec![b"{\"status\":\"pul", b"ling\"}\n{\"sta", b"tus\":\"success\"}\n"]).await;
+        assert_eq!(ok(out), vec![r#"{"status":"pulling"}"#, r#"{"status":"success"}"#]);
+    }
+
+    #[tokio::test]
+    async fn splits_several_lines_in_one_chunk() {
+        let out = collect(vec![b"{\"a\":1}\n{\"a\":2}\n{\"a\":3}\n"]).await;
+        assert_eq!(ok(out).len(), 3);
+    }
+
+    #[tokio::test]
+    async fn keeps_multibyte_characters_split_between_chunks() {
+        let text = "{\"response\":\"čaj ☕\"}\n".as_bytes();
+        // Cut inside the three-byte coffee cup
+        let cut = text.len() - 5;
+        let out = collect(vec![&text[..cut], &text[cut..]]).await;
+        assert_eq!(ok(out), vec!["{\"response\":\"čaj ☕\"}"]);
+    }
+
+    #[tokio::test]
+    async fn emits_trailing_line_without_newline() {
+        let out = collect(vec![b"{\"a\":1}\n{\"a\"", b":2}"]).await;
+        assert_eq!(ok(out), vec![r#"{"a":1}"#, r#"{"a":2}"#]);
+    }
+
+    #[tokio::test]
+    async fn skips_blank_lines_and_trims_crlf() {
+        let out = collect(vec![b"\n{\"a\":1}\r\n  \n\n{\"a\":2}\r\n"]).await;
+        assert_eq!(ok(out), vec![r#"{"a":1}"#, r#"{"a":2}"#]);
+    }
+
+    #[tokio::test]
+    async fn invalid_utf8_fails_only_its_line() {
+        let out = collect(vec![b"{\"a\":1}\n\xff\xfe\n{\"a\":2}\n"]).await;
+        assert_eq!(out.len(), 3);
+        assert!(matches!(out[1], Err(ClientError::Decode(_))));
+        assert_eq!(out[2].as_deref().unwrap(), r#"{"a":2}"#);
+    }
+
+    #[tokio::test]
+    async fn transport_error_ends_the_stream() {
+        let chunks: Vec<Result<Vec<u8>, std::io::Error>> = vec![
+            Ok(b"{\"a\":1}\n{\"a\"".to_vec()),
+            Err(std::io::Error::other("connection reset")),
+            Ok(b":2}\n".to_vec()),
+        ];
+        let out: Vec<_> = lines(futures::stream::iter(chunks)).collect().await;
+        assert_eq!(out.len(), 2);
+        assert!(matches!(out[1], Err(ClientError::Io(_))));
+    }
+
+    #[test]
+    fn decode_reports_error_lines_as_stream_errors() {
+        let err = decode::<PullStatus>(r#"{"error":"pull model manifest: file does not exist"}"#).unwrap_err();
+        assert!(matches!(err, ClientError::Stream(ref m) if m == "pull model manifest: file does not exist"));
+        assert!(decode::<PullStatus>(r#"{"status":"success","error":null}"#).is_ok());
+        assert!(decode::<PullStatus>(r#"{"status":"success","error":false}"#).is_ok());
+    }
+
+    #[test]
+    fn decode_rejects_malformed_json() {
+        assert!(matches!(decode::<Value>("not json"), Err(ClientError::Json(_))));
+    }
+
+    #[test]
+    fn decode_reads_pull_progress() {
+        let line: PullStatus = decode(r#"{"status":"pulling 6a0746a1ec1a","digest":"sha256:6a07","total":100,"completed":40}"#).unwrap();
+        assert_eq!(line.digest.as_deref(), Some("sha256:6a07"));
+        assert_eq!((line.total, line.completed), (Some(100), Some(40)));
+    }
+}
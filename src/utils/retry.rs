@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use rand::Rng;
+
+use crate::errors::ClientError;
+
+/// Consecutive transient failures that open an endpoint's circuit
+const FAILURE_THRESHOLD: u32 = 3;
+/// How long an open circuit fails requests before letting one through
+const OPEN_FOR: Duration = Duration::from_secs(15);
+
+static POLICY: OnceLock<RequestPolicy> = OnceLock::new();
+
+/// Timeout and retry settings shared by every `HttpClient`
+#[derive(Debug, Clone, Copy)]
+pub struct RequestPolicy {
+    /// Per-attempt request timeout
+    pub timeout: Duration,
+    /// Extra attempts for idempotent requests after a transient failure
+    pub retries: u32,
+    /// Backoff before the first retry, doubled for each further one
+    pub base_delay: Duration,
+    /// Upper bound of a single backoff
+    pub max_delay: Duration,
+}
+
+impl Default for RequestPolicy {
+    fn default() -> Self {
+        RequestPolicy {
+            timeout: Duration::from_secs(30),
+            retries: 2,
+            base_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RequestPolicy {
+    /// Set the policy for the rest of the process; only the first call counts
+    pub fn install(policy: RequestPolicy) {
+        let _ = POLICY.set(policy);
+    }
+
+    /// The installed policy, or the default
+    pub fn current() -> RequestPolicy {
+        POLICY.get().copied().unwrap_or_default()
+    }
+
+    /// Exponential backoff with full jitter before retry number `retry` (from 1)
+    pub fn backoff(&self, retry: u32) -> Duration {
+        self.ceiling(retry).mul_f64(rand::rng().random_range(0.0..=1.0))
+    }
+
+    /// Longest backoff before retry number `retry`
+    fn ceiling(&self, retry: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(1 << retry.saturating_sub(1).min(16))
+            .min(self.max_delay)
+    }
+}
+
+/// State of one endpoint's circuit
+#[derive(Debug, Default)]
+struct Circuit {
+    /// Transient failures in a row
+    failures: u32,
+    /// First failure of the current streak
+    since: Option<DateTime<Utc>>,
+    /// Requests fail fast until then
+    open_until: Option<Instant>,
+}
+
+/// Per-endpoint circuit breakers.
+///
+/// After `FAILURE_THRESHOLD` transient failures in a row an endpoint's
+/// circuit opens and requests fail with `ClientError::CircuitOpen` without
+/// touching the network. Once `OPEN_FOR` has passed the circuit is half
+/// open: one request goes through as a probe while the others keep
+/// failing fast. Success closes the circuit, another failure opens it
+/// again. A probe that never reports back is replaced after `OPEN_FOR`.
+#[derive(Debug, Default)]
+pub struct CircuitBreakers {
+    circuits: Mutex<HashMap<String, Circuit>>,
+}
+
+impl CircuitBreakers {
+    /// Fail fast if `endpoint`'s circuit is open, or half open with a
+    /// probe already on its way
+    pub fn check(&self, endpoint: &str) -> Result<(), ClientError> {
+        self.check_at(endpoint, Instant::now())
+    }
+
+    fn check_at(&self, endpoint: &str, now: Instant) -> Result<(), ClientError> {
+        let mut circuits = self.circuits.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(Circuit { open_until: Some(until), since, .. }) = circuits.get_mut(endpoint) else {
+            return Ok(());
+        };
+        if now < *until {
+            return Err(ClientError::CircuitOpen {
+                endpoint: endpoint.to_string(),
+                since: since.unwrap_or_else(Utc::now),
+            });
+        }
+        // Let this request probe; the rest fail fast until it reports back
+        *until = now + OPEN_FOR;
+        Ok(())
+    }
+
+    /// Count the outcome of a request to `endpoint` and pass it on
+    pub fn record<T>(&self, endpoint: &str, result: Result<T, ClientError>) -> Result<T, ClientError> {
+        self.record_at(endpoint, result, Instant::now())
+    }
+
+    fn record_at<T>(&self, endpoint: &str, result: Result<T, ClientError>, now: Instant) -> Result<T, ClientError> {
+        let mut circuits = self.circuits.lock().unwrap_or_else(|e| e.into_inner());
+        match &result {
+            Err(e) if e.is_transient() => {
+                let circuit = circuits.entry(endpoint.to_string()).or_default();
+                circuit.failures += 1;
+                circuit.since.get_or_insert_with(Utc::now);
+                if circuit.failures >= FAILURE_THRESHOLD {
+                    circuit.open_until = Some(now + OPEN_FOR);
+                }
+            }
+            // Any answer from HiveCore, even a rejection, means it is reachable
+            _ => {
+                circuits.remove(endpoint);
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failure() -> Result<(), ClientError> {
+        Err(ClientError::Server { status: 503, body: String::new() })
+    }
+
+    fn is_open(result: Result<(), ClientError>) -> bool {
+        matches!(result, Err(ClientError::CircuitOpen { .. }))
+    }
+
+    #[test]
+    fn backoff_ceiling_doubles_up_to_the_maximum() {
+        let policy = RequestPolicy::default();
+        let ceilings: Vec<u128> = (1..=7).map(|retry| policy.ceiling(retry).as_millis()).collect();
+        assert_eq!(ceilings, [250, 500, 1000, 2000, 4000, 5000, 5000]);
+    }
+
+    #[test]
+    fn backoff_never_exceeds_its_ceiling() {
+        let policy = RequestPolicy::default();
+        for retry in [0, 1, 2, 3, 10, 17, 64, u32::MAX] {
+            for _ in 0..50 {
+                let delay = policy.backoff(retry);
+                assert!(delay <= policy.ceiling(retry) && delay <= policy.max_delay, "{:?} on retry {}", delay, retry);
+            }
+        }
+    }
+
+    #[test]
+    fn a_circuit_opens_after_repeated_failures() {
+        let breakers = CircuitBreakers::default();
+        let start = Instant::now();
+        for _ in 0..FAILURE_THRESHOLD - 1 {
+            let _ = breakers.record_at("/queue", failure(), start);
+            assert!(breakers.check_at("/queue", start).is_ok());
+        }
+        let _ = breakers.record_at("/queue", failure(), start);
+        assert!(is_open(breakers.check_at("/queue", start)));
+        assert!(breakers.check_at("/worker/status", start).is_ok());
+    }
+
+    #[test]
+    fn a_half_open_circuit_lets_one_probe_through() {
+        let breakers = CircuitBreakers::default();
+        let start = Instant::now();
+        for _ in 0..FAILURE_THRESHOLD {
+            let _ = breakers.record_at("/queue", failure(), start);
+        }
+        let later = start + OPEN_FOR;
+        assert!(breakers.check_at("/queue", later).is_ok());
+        assert!(is_open(breakers.check_at("/queue", later)));
+
+        // A failed probe opens the circuit again
+        let _ = breakers.record_at("/queue", failure(), later);
+        assert!(is_open(breakers.check_at("/queue", later + OPEN_FOR / 2)));
+
+        // A successful one closes it
+        let again = later + OPEN_FOR;
+        assert!(breakers.check_at("/queue", again).is_ok());
+        let _ = breakers.record_at("/queue", Ok(()), again);
+        assert!(breakers.check_at("/queue", again).is_ok());
+        assert!(breakers.check_at("/queue", again).is_ok());
+    }
+
+    #[test]
+    fn a_lost_probe_is_replaced() {
+        let breakers = CircuitBreakers::default();
+        let start = Instant::now();
+        for _ in 0..FAILURE_THRESHOLD {
+            let _ = breakers.record_at("/queue", failure(), start);
+        }
+        assert!(breakers.check_at("/queue", start + OPEN_FOR).is_ok());
+        assert!(is_open(breakers.check_at("/queue", start + OPEN_FOR * 2 - Duration::from_millis(1))));
+        assert!(breakers.check_at("/queue", start + OPEN_FOR * 2).is_ok());
+    }
+
+    #[test]
+    fn rejections_do_not_count_as_failures() {
+        let breakers = CircuitBreakers::default();
+        let start = Instant::now();
+        for _ in 0..FAILURE_THRESHOLD {
+            let _ = breakers.record_at("/queue", failure(), start);
+            let _ = breakers.record_at::<()>("/queue", Err(ClientError::Rejected { status: 400, body: String::new() }), start);
+        }
+        assert!(breakers.check_at("/queue", start).is_ok());
+    }
+}
@@ -0,0 +1,242 @@
+use std::{collections::HashMap, future::Future, sync::Arc, time::Duration};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Largest request head accepted
+const MAX_REQUEST_BYTES: usize = 64 * 1024;
+/// Largest request body accepted
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+/// Time a client gets to send its whole request before it is dropped
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A parsed HTTP/1.1 request
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    /// Path without the query string
+    pub path: String,
+    /// Query string without the leading `?`, empty if there is none
+    pub query: String,
+    /// Header values by lowercase name
+    pub headers: HashMap<String, String>,
+    /// Body as announced by `content-length`; empty without one
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    /// Value of the header `name`, matched case-insensitively
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_ascii_lowercase()).map(String::as_str)
+    }
+}
+
+/// A complete HTTP response; the connection is closed after it is sent
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: String,
+    pub body: Vec<u8>,
+    /// Send the body line by line with this pause in between, like a
+    /// streamed NDJSON response, instead of all at once
+    pub pace: Option<Duration>,
+}
+
+impl Response {
+    pub fn new(status: u16, content_type: &str, body: impl Into<Vec<u8>>) -> Self {
+        Response { status, content_type: content_type.to_string(), body: body.into(), pace: None }
+    }
+
+    /// Stream the body one line at a time, `delay` apart
+    pub fn paced(mut self, delay: Duration) -> Self {
+        self.pace = Some(delay);
+        self
+    }
+
+    pub fn not_found() -> Self {
+        Response::new(404, "text/plain", "not found\n")
+    }
+}
+
+/// Accept connections forever, answering each request with `handler`
+pub async fn serve<F, Fut>(listener: TcpListener, handler: F)
+where
+    F: Fn(Request) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Response> + Send,
+{
+    let handler = Arc::new(handler);
+    loop {
+        let Ok((socket, _)) = listener.accept().await else { continue };
+        let handler = handler.clone();
+        tokio::spawn(async move {
+            let _ = handle_connection(socket, handler.as_ref(), REQUEST_TIMEOUT).await;
+        });
+    }
+}
+
+async fn handle_connection<F, Fut>(mut socket: TcpStream, handler: &F, timeout: Duration) -> std::io::Result<()>
+where
+    F: Fn(Request) -> Fut,
+    Fut: Future<Output = Response>,
+{
+    // One deadline for head and body, so trickling bytes does not extend it
+    let Ok(request) = tokio::time::timeout(timeout, read_request(&mut socket)).await else {
+        let response = Response::new(408, "text/plain", "request timeout\n");
+        return write_response(&mut socket, &response).await;
+    };
+    let Some(request) = request? else {
+        let response = Response::new(400, "text/plain", "bad request\n");
+        return write_response(&mut socket, &response).await;
+    };
+    let response = handler(request).await;
+    write_response(&mut socket, &response).await
+}
+
+async fn read_request(socket: &mut TcpStream) -> std::io::Result<Option<Request>> {
+    let mut data = Vec::new();
+    let mut buf = [0u8; 4096];
+    let head_end = loop {
+        if let Some(pos) = data.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos;
+        }
+        let n = socket.read(&mut buf).await?;
+        if n == 0 {
+            return Ok(None);
+        }
+        data.extend_from_slice(&buf[..n]);
+        if data.len() > MAX_REQUEST_BYTES {
+            return Ok(None);
+        }
+    };
+
+    let head = String::from_utf8_lossy(&data[..head_end]).to_string();
+    let mut lines = head.lines();
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let (Some(method), Some(target)) = (request_line.next(), request_line.next()) else {
+        return Ok(None);
+    };
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let headers: HashMap<String, String> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+
+    let length = match headers.get("content-length").map(|v| v.parse::<usize>()) {
+        None => 0,
+        Some(Ok(length)) if length <= MAX_BODY_BYTES => length,
+        Some(_) => return Ok(None),
+    };
+    let mut body = data.split_off(head_end + 4);
+    while body.len() < length {
+        let n = socket.read(&mut buf).await?;
+        if n == 0 {
+            return Ok(None);
+        }
+        body.extend_from_slice(&buf[..n]);
+    }
+    body.truncate(length);
+
+    Ok(Some(Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        query: query.to_string(),
+        headers,
+        body,
+    }))
+}
+
+async fn write_response(socket: &mut TcpStream, response: &Response) -> std::io::Result<()> {
+    // A paced body has no length up front; closing the connection ends it
+    let length = match response.pace {
+        Some(_) => String::new(),
+        None => format!("content-length: {}\r\n", response.body.len()),
+    };
+    let head = format!(
+        "HTTP/1.1 {} {}\r\ncontent-type: {}\r\n{}connection: close\r\n\r\n",
+        response.status,
+        reason(response.status),
+        response.content_type,
+        length,
+    );
+    socket.write_all(head.as_bytes()).await?;
+    match response.pace {
+        Some(delay) => {
+            for line in response.body.split_inclusive(|&b| b == b'\n') {
+                socket.write_all(line).await?;
+                socket.flush().await?;
+                tokio::time::sleep(delay).await;
+            }
+        }
+        None => socket.write_all(&response.body).await?,
+    }
+    socket.shutdown().await
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        408 => "Request Timeout",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Connect a client to a server socket handled with `timeout`
+    async fn connect(timeout: Duration) -> (TcpStream, tokio::task::JoinHandle<std::io::Result<()>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap()).await.unwrap();
+        let (socket, _) = listener.accept().await.unwrap();
+        let handle = tokio::spawn(async move {
+            handle_connection(socket, &|_| async { Response::new(200, "text/plain", "ok\n") }, timeout).await
+        });
+        (client, handle)
+    }
+
+    async fn reply(client: &mut TcpStream) -> String {
+        let mut reply = String::new();
+        client.read_to_string(&mut reply).await.unwrap();
+        reply
+    }
+
+    #[tokio::test]
+    async fn answers_a_request() {
+        let (mut client, _) = connect(Duration::from_secs(5)).await;
+        client.write_all(b"GET /metrics HTTP/1.1\r\nhost: x\r\n\r\n").await.unwrap();
+        assert!(reply(&mut client).await.starts_with("HTTP/1.1 200 OK"));
+    }
+
+    #[tokio::test]
+    async fn drops_a_client_that_sends_nothing() {
+        let (mut client, handle) = connect(Duration::from_millis(100)).await;
+        let reply = tokio::time::timeout(Duration::from_secs(5), reply(&mut client)).await.unwrap();
+        assert!(reply.starts_with("HTTP/1.1 408 Request Timeout"));
+        assert!(handle.await.unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn drops_a_client_that_trickles_its_body() {
+        let (mut client, _) = connect(Duration::from_millis(300)).await;
+        client.write_all(b"POST /api HTTP/1.1\r\ncontent-length: 100\r\n\r\n").await.unwrap();
+        for _ in 0..3 {
+            tokio::time::sleep(Duration::from_millis(150)).await;
+            let _ = client.write_all(b"x").await;
+        }
+        assert!(reply(&mut client).await.starts_with("HTTP/1.1 408"));
+    }
+
+    #[tokio::test]
+    async fn rejects_an_oversized_head() {
+        let (mut client, _) = connect(Duration::from_secs(5)).await;
+        let header = format!("GET / HTTP/1.1\r\nx: {}\r\n", "a".repeat(MAX_REQUEST_BYTES));
+        let _ = client.write_all(header.as_bytes()).await;
+        assert!(reply(&mut client).await.starts_with("HTTP/1.1 400"));
+    }
+}